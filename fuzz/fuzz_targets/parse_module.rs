@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ruswacipher::wasm::{self, names, WasmParser, WasmWriter};
+
+// Malformed input must only ever produce an `Err`, never a panic
+fuzz_target!(|data: &[u8]| {
+    let _ = WasmParser::validate_wasm(data);
+    let _ = WasmParser::detect_features(data);
+
+    if let Ok(module) = WasmParser::parse_module(data) {
+        let mut scrambled = module.clone();
+        let _ = names::scramble_names(&mut scrambled, &[]);
+        let _ = wasm::diff(&module, &scrambled);
+
+        // Anything that parses must survive a serialize/parse round trip
+        let output = WasmWriter::serialize_module(&module).unwrap();
+        let reparsed = WasmParser::parse_module(&output).unwrap();
+        assert_eq!(WasmWriter::serialize_module(&reparsed).unwrap(), output);
+    }
+});
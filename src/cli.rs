@@ -1,7 +1,9 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{ArgAction, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use std::path::PathBuf;
 
-use crate::config::{DecryptionConfig, EncryptionAlgorithm, EncryptionConfig};
+use crate::config::{DecryptionConfig, EncryptionAlgorithm, EncryptionConfig, Profile};
+use crate::crypto::SriAlgorithm;
 use crate::error::Result;
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -14,6 +16,31 @@ pub enum KeyFormat {
     Raw,
 }
 
+/// Git commit the binary was built from ("unknown" outside a git checkout)
+pub const GIT_COMMIT: &str = env!("RUSWACIPHER_GIT_COMMIT");
+
+/// UTC date the binary was built
+pub const BUILD_DATE: &str = env!("RUSWACIPHER_BUILD_DATE");
+
+/// Version line with build details, e.g. `ruswacipher 0.1.0 (commit 1a2b3c4d5e6f, built 2025-06-01)`
+pub fn version_string() -> String {
+    format!(
+        "ruswacipher {} (commit {}, built {})",
+        env!("CARGO_PKG_VERSION"),
+        GIT_COMMIT,
+        BUILD_DATE
+    )
+}
+
+/// Output format for command results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable log lines (default)
+    Text,
+    /// A single JSON object on stdout; diagnostics stay on stderr
+    Json,
+}
+
 #[derive(Parser)]
 #[command(name = "ruswacipher")]
 #[command(about = "A Rust tool for encrypting and protecting WebAssembly modules")]
@@ -22,9 +49,28 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
 
-    /// Enable verbose logging
-    #[arg(short, long, global = true)]
-    pub verbose: bool,
+    /// Increase logging verbosity (-v for debug, -vv for trace)
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Output format for command results
+    #[arg(long, global = true, default_value = "text")]
+    pub format: OutputFormat,
+
+    /// Append a JSON line per encrypt/decrypt operation to this file (no key material)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub audit_log: Option<PathBuf>,
+}
+
+impl Cli {
+    /// Log level selected by the number of `-v` flags
+    pub fn log_level(&self) -> log::LevelFilter {
+        match self.verbose {
+            0 => log::LevelFilter::Info,
+            1 => log::LevelFilter::Debug,
+            _ => log::LevelFilter::Trace,
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -39,9 +85,13 @@ pub enum Commands {
         #[arg(short, long)]
         output: PathBuf,
 
-        /// Encryption algorithm to use
-        #[arg(short, long, default_value = "aes-gcm")]
-        algorithm: EncryptionAlgorithm,
+        /// Encryption algorithm to use [default: aes-gcm, or the profile's]
+        #[arg(short, long)]
+        algorithm: Option<EncryptionAlgorithm>,
+
+        /// Preset settings (web, embedded, max); explicit flags take precedence
+        #[arg(long)]
+        profile: Option<Profile>,
 
         /// Key file path (if not provided, a new key will be generated)
         #[arg(short, long)]
@@ -55,18 +105,110 @@ pub enum Commands {
         #[arg(long, conflicts_with_all = ["key", "key_hex"])]
         key_base64: Option<String>,
 
-        /// Generate a new key and save it to this file
+        /// Environment variable holding the key, encoded per --key-env-format
+        #[arg(long, conflicts_with_all = ["key", "key_hex", "key_base64"])]
+        key_env: Option<String>,
+
+        /// Encoding of the key read from --key-env
+        #[arg(long, default_value = "hex")]
+        key_env_format: KeyFormat,
+
+        /// Generate a new key and save it to this file (default: `<output>.key`)
         #[arg(long)]
         generate_key: Option<PathBuf>,
 
+        /// Overwrite an existing key file when saving a generated key
+        #[arg(long)]
+        force_key: bool,
+
         /// Format for generated key output
         #[arg(long, default_value = "hex")]
         key_format: KeyFormat,
+
+        /// Replace names in the `name` section with opaque identifiers
+        #[arg(long, overrides_with = "no_scramble_names")]
+        scramble_names: bool,
+
+        /// Keep names even if the profile scrambles them
+        #[arg(long, overrides_with = "scramble_names")]
+        no_scramble_names: bool,
+
+        /// Export or debug name obfuscation must keep (repeatable; `prefix*` matches a prefix)
+        #[arg(long, value_name = "NAME")]
+        preserve: Vec<String>,
+
+        /// Rewrite constant global initializers as equivalent subtractions (needs extended-const)
+        #[arg(long, conflicts_with = "strip_only")]
+        obfuscate_globals: bool,
+
+        /// Skip behavior-altering obfuscation for modules smaller than this many bytes
+        #[arg(long, value_name = "BYTES", default_value_t = 0)]
+        min_obfuscation_size: usize,
+
+        /// Only remove debug/toolchain metadata sections; no behavior-altering transforms
+        #[arg(long, conflicts_with_all = ["scramble_names", "profile"])]
+        strip_only: bool,
+
+        /// Log a section-level diff of the module after obfuscation
+        #[arg(long)]
+        diff: bool,
+
+        /// Write the obfuscated module as WAT to this path before encrypting (needs `wat-dump`)
+        #[arg(long, value_name = "PATH")]
+        dump_wat: Option<PathBuf>,
+
+        /// Append the key to the encrypted file (insecure: anyone with the file can decrypt it)
+        #[arg(long)]
+        embed_key: bool,
+
+        /// Derive the IV from the plaintext so identical inputs encrypt identically
+        #[arg(long)]
+        convergent: bool,
+
+        /// Treat the input as WAT text and assemble it first (implied by a `.wat` extension)
+        #[arg(long)]
+        wat: bool,
+
+        /// Wrap the encrypted output in a valid WASM module (`.rwc.payload` custom section)
+        #[arg(long, overrides_with = "no_envelope")]
+        envelope: bool,
+
+        /// Write the bare container even if the profile enables --envelope
+        #[arg(long, overrides_with = "envelope")]
+        no_envelope: bool,
+
+        /// Notice text, or a file holding it, to embed as a `.rwc.notice` custom section
+        #[arg(long, value_name = "TEXT|FILE")]
+        notice: Option<String>,
+
+        /// Chunk size in bytes for reading the input
+        #[arg(long, default_value_t = crate::io::DEFAULT_BUFFER_SIZE)]
+        buffer_size: usize,
+
+        /// Ed25519 private key file (hex or base64 seed); writes a detached `<output>.sig`
+        #[arg(long)]
+        sign_key: Option<PathBuf>,
+
+        /// Write the output as numbered parts (`<output>.000`, ...) of at most this many bytes
+        #[arg(long, value_name = "BYTES", conflicts_with_all = ["sign_key", "print_sri"])]
+        split_size: Option<usize>,
+
+        /// Print the SRI hash of the encrypted output (sha256, sha384 or sha512)
+        #[arg(long, value_name = "ALGORITHM", num_args = 0..=1, default_missing_value = "sha384")]
+        print_sri: Option<SriAlgorithm>,
+
+        /// OS keyring service holding the key (a generated key is stored there)
+        #[arg(long, requires = "keyring_account")]
+        keyring_service: Option<String>,
+
+        /// OS keyring account holding the key
+        #[arg(long, requires = "keyring_service")]
+        keyring_account: Option<String>,
     },
 
     /// Decrypt a WASM file
     Decrypt {
-        /// Input encrypted file path
+        /// Input encrypted file path, or an http(s):// URL (requires `--features http`)
         #[arg(short, long)]
         input: PathBuf,
 
@@ -74,12 +216,73 @@ pub enum Commands {
         #[arg(short, long)]
         output: PathBuf,
 
-        /// Key file path
+        /// Key file path (if omitted, a key embedded with --embed-key is used)
         #[arg(short, long)]
-        key: PathBuf,
+        key: Option<PathBuf>,
+
+        /// Environment variable holding the key, encoded per --key-env-format
+        #[arg(long, conflicts_with = "key")]
+        key_env: Option<String>,
+
+        /// Encoding of the key read from --key-env
+        #[arg(long, default_value = "hex")]
+        key_env_format: KeyFormat,
+
+        /// OS keyring service holding the key
+        #[arg(long, requires = "keyring_account", conflicts_with_all = ["key", "key_env"])]
+        keyring_service: Option<String>,
+
+        /// OS keyring account holding the key
+        #[arg(long, requires = "keyring_service")]
+        keyring_account: Option<String>,
+
+        /// Remove `.rwc.*` custom sections added by RusWaCipher from the output
+        #[arg(long)]
+        clean: bool,
+
+        /// Chunk size in bytes for reading the input
+        #[arg(long, default_value_t = crate::io::DEFAULT_BUFFER_SIZE)]
+        buffer_size: usize,
+
+        /// Ed25519 public key file (hex or base64); verify `<input>.sig` before decrypting
+        #[arg(long)]
+        verify_key: Option<PathBuf>,
+
+        /// Skip TLS certificate verification for URL inputs (self-signed internal registries)
+        #[arg(long)]
+        insecure: bool,
+
+        /// Read the parts `<input>.000`, `<input>.001`, ... written by encrypt --split-size
+        #[arg(long, conflicts_with_all = ["verify_key", "insecure"])]
+        parts: bool,
+    },
+
+    /// Print version and build information
+    Version,
+
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        shell: Shell,
     },
 }
 
+/// Write the completion script for `shell` to `out`
+pub fn write_completions(shell: Shell, out: &mut dyn std::io::Write) {
+    clap_complete::generate(shell, &mut Cli::command(), "ruswacipher", out);
+}
+
+/// Resolve an on/off flag pair against a profile default; an explicit flag wins
+fn flag_or_preset(on: bool, off: bool, preset: bool) -> bool {
+    if on {
+        true
+    } else if off {
+        false
+    } else {
+        preset
+    }
+}
+
 impl Commands {
     pub fn to_encryption_config(&self) -> Result<EncryptionConfig> {
         match self {
@@ -87,23 +290,80 @@ impl Commands {
                 input,
                 output,
                 algorithm,
+                profile,
                 key,
                 key_hex,
                 key_base64,
+                key_env,
+                key_env_format,
                 generate_key,
+                force_key,
                 key_format,
+                scramble_names,
+                no_scramble_names,
+                preserve,
+                obfuscate_globals,
+                min_obfuscation_size,
+                strip_only,
+                diff,
+                dump_wat,
+                embed_key,
+                convergent,
+                wat,
+                envelope,
+                no_envelope,
+                notice,
+                buffer_size,
+                sign_key,
+                split_size,
+                print_sri,
+                keyring_service,
+                keyring_account,
             } => {
                 let generate_key_flag = generate_key.is_some();
+                let preset = profile.map(|p| p.settings());
                 Ok(EncryptionConfig {
-                    algorithm: algorithm.clone(),
+                    algorithm: algorithm
+                        .clone()
+                        .or_else(|| preset.as_ref().map(|p| p.algorithm.clone()))
+                        .unwrap_or(EncryptionAlgorithm::AesGcm),
                     input_file: input.clone(),
                     output_file: output.clone(),
                     key_file: key.clone(),
                     key_hex: key_hex.clone(),
                     key_base64: key_base64.clone(),
+                    key_env: key_env.clone(),
+                    key_env_format: key_env_format.clone(),
                     generate_key: generate_key_flag,
                     key_output_file: generate_key.clone(),
+                    force_key: *force_key,
                     key_format: key_format.clone(),
+                    scramble_names: flag_or_preset(
+                        *scramble_names,
+                        *no_scramble_names,
+                        preset.as_ref().is_some_and(|p| p.scramble_names),
+                    ),
+                    preserve: preserve.clone(),
+                    obfuscate_globals: *obfuscate_globals,
+                    min_obfuscation_size: *min_obfuscation_size,
+                    strip_only: *strip_only,
+                    show_diff: *diff,
+                    dump_wat: dump_wat.clone(),
+                    embed_key: *embed_key,
+                    convergent: *convergent,
+                    wat: *wat || input.extension().is_some_and(|ext| ext == "wat"),
+                    envelope: flag_or_preset(
+                        *envelope,
+                        *no_envelope,
+                        preset.as_ref().is_some_and(|p| p.envelope),
+                    ),
+                    notice: notice.clone(),
+                    buffer_size: *buffer_size,
+                    sign_key: sign_key.clone(),
+                    split_size: *split_size,
+                    print_sri: *print_sri,
+                    keyring_service: keyring_service.clone(),
+                    keyring_account: keyring_account.clone(),
                 })
             }
             _ => Err(crate::error::RusWaCipherError::InvalidInput(
@@ -114,10 +374,32 @@ impl Commands {
 
     pub fn to_decryption_config(&self) -> Result<DecryptionConfig> {
         match self {
-            Commands::Decrypt { input, output, key } => Ok(DecryptionConfig {
+            Commands::Decrypt {
+                input,
+                output,
+                key,
+                key_env,
+                key_env_format,
+                keyring_service,
+                keyring_account,
+                clean,
+                buffer_size,
+                verify_key,
+                insecure,
+                parts,
+            } => Ok(DecryptionConfig {
                 input_file: input.clone(),
                 output_file: output.clone(),
                 key_file: key.clone(),
+                key_env: key_env.clone(),
+                key_env_format: key_env_format.clone(),
+                keyring_service: keyring_service.clone(),
+                keyring_account: keyring_account.clone(),
+                clean: *clean,
+                buffer_size: *buffer_size,
+                verify_key: verify_key.clone(),
+                insecure: *insecure,
+                parts: *parts,
             }),
             _ => Err(crate::error::RusWaCipherError::InvalidInput(
                 "Not a decryption command".to_string(),
@@ -141,42 +423,27 @@ mod tests {
 
     #[test]
     fn test_encryption_config_from_encrypt_command() {
-        let command = Commands::Encrypt {
-            input: PathBuf::from("input.wasm"),
-            output: PathBuf::from("output.wasm.enc"),
-            algorithm: crate::config::EncryptionAlgorithm::AesGcm,
-            key: Some(PathBuf::from("key.txt")),
-            key_hex: None,
-            key_base64: None,
-            generate_key: None,
-            key_format: KeyFormat::Hex,
-        };
-
-        let config = command.to_encryption_config().unwrap();
+        let config = encrypt_config(&["-a", "aes-gcm", "-k", "key.txt"]);
         assert!(matches!(
             config.algorithm,
             crate::config::EncryptionAlgorithm::AesGcm
         ));
-        assert_eq!(config.input_file, PathBuf::from("input.wasm"));
-        assert_eq!(config.output_file, PathBuf::from("output.wasm.enc"));
+        assert_eq!(config.input_file, PathBuf::from("in.wasm"));
+        assert_eq!(config.output_file, PathBuf::from("out.enc"));
         assert_eq!(config.key_file, Some(PathBuf::from("key.txt")));
         assert!(!config.generate_key);
     }
 
     #[test]
     fn test_encryption_config_with_generate_key() {
-        let command = Commands::Encrypt {
-            input: PathBuf::from("input.wasm"),
-            output: PathBuf::from("output.wasm.enc"),
-            algorithm: crate::config::EncryptionAlgorithm::ChaCha20Poly1305,
-            key: None,
-            key_hex: None,
-            key_base64: None,
-            generate_key: Some(PathBuf::from("generated.key")),
-            key_format: KeyFormat::Base64,
-        };
-
-        let config = command.to_encryption_config().unwrap();
+        let config = encrypt_config(&[
+            "-a",
+            "chacha20poly1305",
+            "--generate-key",
+            "generated.key",
+            "--key-format",
+            "base64",
+        ]);
         assert!(matches!(
             config.algorithm,
             crate::config::EncryptionAlgorithm::ChaCha20Poly1305
@@ -188,18 +455,7 @@ mod tests {
 
     #[test]
     fn test_encryption_config_with_hex_key() {
-        let command = Commands::Encrypt {
-            input: PathBuf::from("input.wasm"),
-            output: PathBuf::from("output.wasm.enc"),
-            algorithm: crate::config::EncryptionAlgorithm::AesGcm,
-            key: None,
-            key_hex: Some("0123456789abcdef".to_string()),
-            key_base64: None,
-            generate_key: None,
-            key_format: KeyFormat::Hex,
-        };
-
-        let config = command.to_encryption_config().unwrap();
+        let config = encrypt_config(&["--key-hex", "0123456789abcdef"]);
         assert_eq!(config.key_hex, Some("0123456789abcdef".to_string()));
         assert!(config.key_base64.is_none());
         assert!(config.key_file.is_none());
@@ -207,18 +463,7 @@ mod tests {
 
     #[test]
     fn test_encryption_config_with_base64_key() {
-        let command = Commands::Encrypt {
-            input: PathBuf::from("input.wasm"),
-            output: PathBuf::from("output.wasm.enc"),
-            algorithm: crate::config::EncryptionAlgorithm::AesGcm,
-            key: None,
-            key_hex: None,
-            key_base64: Some("SGVsbG8gV29ybGQ=".to_string()),
-            generate_key: None,
-            key_format: KeyFormat::Hex,
-        };
-
-        let config = command.to_encryption_config().unwrap();
+        let config = encrypt_config(&["--key-base64", "SGVsbG8gV29ybGQ="]);
         assert_eq!(config.key_base64, Some("SGVsbG8gV29ybGQ=".to_string()));
         assert!(config.key_hex.is_none());
         assert!(config.key_file.is_none());
@@ -229,7 +474,16 @@ mod tests {
         let command = Commands::Decrypt {
             input: PathBuf::from("input.wasm.enc"),
             output: PathBuf::from("output.wasm"),
-            key: PathBuf::from("key.txt"),
+            key: Some(PathBuf::from("key.txt")),
+            key_env: None,
+            key_env_format: KeyFormat::Hex,
+            keyring_service: None,
+            keyring_account: None,
+            clean: false,
+            buffer_size: crate::io::DEFAULT_BUFFER_SIZE,
+            verify_key: None,
+            insecure: false,
+            parts: false,
         };
 
         let result = command.to_decryption_config();
@@ -238,7 +492,81 @@ mod tests {
         let config = result.unwrap();
         assert_eq!(config.input_file, PathBuf::from("input.wasm.enc"));
         assert_eq!(config.output_file, PathBuf::from("output.wasm"));
-        assert_eq!(config.key_file, PathBuf::from("key.txt"));
+        assert_eq!(config.key_file, Some(PathBuf::from("key.txt")));
+    }
+
+    fn encrypt_config(extra: &[&str]) -> EncryptionConfig {
+        let args = ["ruswacipher", "encrypt", "-i", "in.wasm", "-o", "out.enc"];
+        let cli = Cli::try_parse_from(args.iter().chain(extra)).unwrap();
+        cli.command.to_encryption_config().unwrap()
+    }
+
+    #[test]
+    fn test_profiles_resolve_to_documented_settings() {
+        let config = encrypt_config(&[]);
+        assert_eq!(config.algorithm, EncryptionAlgorithm::AesGcm);
+        assert!(!config.scramble_names && !config.envelope);
+
+        let config = encrypt_config(&["--profile", "web"]);
+        assert_eq!(config.algorithm, EncryptionAlgorithm::AesGcm);
+        assert!(config.scramble_names && !config.envelope);
+
+        let config = encrypt_config(&["--profile", "embedded"]);
+        assert_eq!(config.algorithm, EncryptionAlgorithm::ChaCha20Poly1305);
+        assert!(!config.scramble_names && !config.envelope);
+
+        let config = encrypt_config(&["--profile", "max"]);
+        assert_eq!(config.algorithm, EncryptionAlgorithm::AesGcm);
+        assert!(config.scramble_names && config.envelope);
+    }
+
+    #[test]
+    fn test_explicit_flags_override_profile() {
+        let config = encrypt_config(&["--profile", "embedded", "-a", "aes-128-gcm"]);
+        assert_eq!(config.algorithm, EncryptionAlgorithm::Aes128Gcm);
+
+        let config = encrypt_config(&["--profile", "embedded", "--scramble-names"]);
+        assert_eq!(config.algorithm, EncryptionAlgorithm::ChaCha20Poly1305);
+        assert!(config.scramble_names);
+
+        let config = encrypt_config(&["--profile", "max", "--no-scramble-names", "--no-envelope"]);
+        assert!(!config.scramble_names && !config.envelope);
+
+        // The last of a flag pair wins
+        let config = encrypt_config(&["--no-envelope", "--envelope"]);
+        assert!(config.envelope);
+    }
+
+    #[test]
+    fn test_bash_completions() {
+        let mut out = Vec::new();
+        write_completions(Shell::Bash, &mut out);
+
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("encrypt"));
+        assert!(script.contains("--key-format"));
+    }
+
+    #[test]
+    fn test_verbose_levels() {
+        let args = [
+            "ruswacipher",
+            "decrypt",
+            "-i",
+            "in",
+            "-o",
+            "out",
+            "-k",
+            "key",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert_eq!(cli.log_level(), log::LevelFilter::Info);
+
+        let cli = Cli::try_parse_from(args.iter().chain(&["-v"])).unwrap();
+        assert_eq!(cli.log_level(), log::LevelFilter::Debug);
+
+        let cli = Cli::try_parse_from(args.iter().chain(&["-vv"])).unwrap();
+        assert_eq!(cli.log_level(), log::LevelFilter::Trace);
     }
 
     #[test]
@@ -246,7 +574,16 @@ mod tests {
         let command = Commands::Decrypt {
             input: PathBuf::from("input.wasm.enc"),
             output: PathBuf::from("output.wasm"),
-            key: PathBuf::from("key.txt"),
+            key: Some(PathBuf::from("key.txt")),
+            key_env: None,
+            key_env_format: KeyFormat::Hex,
+            keyring_service: None,
+            keyring_account: None,
+            clean: false,
+            buffer_size: crate::io::DEFAULT_BUFFER_SIZE,
+            verify_key: None,
+            insecure: false,
+            parts: false,
         };
 
         let result = command.to_encryption_config();
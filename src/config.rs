@@ -1,30 +1,137 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EncryptionAlgorithm {
+    /// AES-256-GCM
     AesGcm,
+    /// AES-128-GCM
+    Aes128Gcm,
+    /// AES-192-GCM
+    Aes192Gcm,
     ChaCha20Poly1305,
 }
 
+impl EncryptionAlgorithm {
+    /// Required key length in bytes
+    pub fn key_length(&self) -> usize {
+        match self {
+            EncryptionAlgorithm::AesGcm => 32,
+            EncryptionAlgorithm::Aes128Gcm => 16,
+            EncryptionAlgorithm::Aes192Gcm => 24,
+            EncryptionAlgorithm::ChaCha20Poly1305 => 32,
+        }
+    }
+
+    /// Unambiguous name including the key size where relevant
+    pub fn canonical_name(&self) -> &'static str {
+        match self {
+            EncryptionAlgorithm::AesGcm => "aes-256-gcm",
+            EncryptionAlgorithm::Aes128Gcm => "aes-128-gcm",
+            EncryptionAlgorithm::Aes192Gcm => "aes-192-gcm",
+            EncryptionAlgorithm::ChaCha20Poly1305 => "chacha20poly1305",
+        }
+    }
+}
+
 impl std::fmt::Display for EncryptionAlgorithm {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             EncryptionAlgorithm::AesGcm => write!(f, "aes-gcm"),
+            EncryptionAlgorithm::Aes128Gcm => write!(f, "aes-128-gcm"),
+            EncryptionAlgorithm::Aes192Gcm => write!(f, "aes-192-gcm"),
             EncryptionAlgorithm::ChaCha20Poly1305 => write!(f, "chacha20poly1305"),
         }
     }
 }
 
+/// Accepted spellings, compared case-insensitively with `-`, `_` and spaces removed
+const ALGORITHM_ALIASES: &[(&str, EncryptionAlgorithm)] = &[
+    ("aesgcm", EncryptionAlgorithm::AesGcm),
+    ("aes256gcm", EncryptionAlgorithm::AesGcm),
+    ("aes256", EncryptionAlgorithm::AesGcm),
+    ("aes128gcm", EncryptionAlgorithm::Aes128Gcm),
+    ("aes128", EncryptionAlgorithm::Aes128Gcm),
+    ("aes192gcm", EncryptionAlgorithm::Aes192Gcm),
+    ("aes192", EncryptionAlgorithm::Aes192Gcm),
+    ("chacha20poly1305", EncryptionAlgorithm::ChaCha20Poly1305),
+    ("chacha20", EncryptionAlgorithm::ChaCha20Poly1305),
+];
+
 impl std::str::FromStr for EncryptionAlgorithm {
     type Err = crate::error::RusWaCipherError;
 
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized: String = s
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .collect::<String>()
+            .to_lowercase();
+
+        ALGORITHM_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == normalized)
+            .map(|(_, algorithm)| algorithm.clone())
+            .ok_or_else(|| {
+                crate::error::RusWaCipherError::UnsupportedAlgorithm(format!(
+                    "Unknown encryption algorithm: {}",
+                    s
+                ))
+            })
+    }
+}
+
+/// Named preset of encryption settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// Browser delivery: AES-256-GCM (WebCrypto) with scrambled names
+    Web,
+    /// Targets without AES hardware: ChaCha20-Poly1305, no obfuscation
+    Embedded,
+    /// Strongest protection: AES-256-GCM, scrambled names, WASM envelope
+    Max,
+}
+
+/// Settings a [`Profile`] expands into
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileSettings {
+    pub algorithm: EncryptionAlgorithm,
+    pub scramble_names: bool,
+    pub envelope: bool,
+}
+
+impl Profile {
+    pub fn settings(&self) -> ProfileSettings {
+        match self {
+            Profile::Web => ProfileSettings {
+                algorithm: EncryptionAlgorithm::AesGcm,
+                scramble_names: true,
+                envelope: false,
+            },
+            Profile::Embedded => ProfileSettings {
+                algorithm: EncryptionAlgorithm::ChaCha20Poly1305,
+                scramble_names: false,
+                envelope: false,
+            },
+            Profile::Max => ProfileSettings {
+                algorithm: EncryptionAlgorithm::AesGcm,
+                scramble_names: true,
+                envelope: true,
+            },
+        }
+    }
+}
+
+impl std::str::FromStr for Profile {
+    type Err = crate::error::RusWaCipherError;
+
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
-            "aes-gcm" | "aesgcm" => Ok(EncryptionAlgorithm::AesGcm),
-            "chacha20poly1305" | "chacha20-poly1305" => Ok(EncryptionAlgorithm::ChaCha20Poly1305),
+            "web" => Ok(Profile::Web),
+            "embedded" => Ok(Profile::Embedded),
+            "max" => Ok(Profile::Max),
             _ => Err(crate::error::RusWaCipherError::InvalidInput(format!(
-                "Unknown encryption algorithm: {}",
+                "Unknown profile: {} (expected web, embedded or max)",
                 s
             ))),
         }
@@ -39,22 +146,272 @@ pub struct EncryptionConfig {
     pub key_file: Option<PathBuf>,
     pub key_hex: Option<String>,
     pub key_base64: Option<String>,
+    pub key_env: Option<String>,
+    pub key_env_format: crate::cli::KeyFormat,
     pub generate_key: bool,
     pub key_output_file: Option<PathBuf>,
+    pub force_key: bool,
     pub key_format: crate::cli::KeyFormat,
+    pub scramble_names: bool,
+    pub preserve: Vec<String>,
+    pub obfuscate_globals: bool,
+    pub min_obfuscation_size: usize,
+    pub strip_only: bool,
+    pub show_diff: bool,
+    pub dump_wat: Option<PathBuf>,
+    pub embed_key: bool,
+    pub convergent: bool,
+    pub wat: bool,
+    pub envelope: bool,
+    pub notice: Option<String>,
+    pub buffer_size: usize,
+    pub sign_key: Option<PathBuf>,
+    pub split_size: Option<usize>,
+    pub print_sri: Option<crate::crypto::SriAlgorithm>,
+    pub keyring_service: Option<String>,
+    pub keyring_account: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct DecryptionConfig {
     pub input_file: PathBuf,
     pub output_file: PathBuf,
-    pub key_file: PathBuf,
+    pub key_file: Option<PathBuf>,
+    pub key_env: Option<String>,
+    pub key_env_format: crate::cli::KeyFormat,
+    pub keyring_service: Option<String>,
+    pub keyring_account: Option<String>,
+    pub clean: bool,
+    pub buffer_size: usize,
+    pub verify_key: Option<PathBuf>,
+    pub insecure: bool,
+    pub parts: bool,
+}
+
+impl EncryptionConfig {
+    /// Start building a configuration with the CLI defaults
+    pub fn builder<P: Into<PathBuf>, Q: Into<PathBuf>>(
+        input_file: P,
+        output_file: Q,
+    ) -> EncryptionConfigBuilder {
+        EncryptionConfigBuilder {
+            config: EncryptionConfig {
+                algorithm: EncryptionAlgorithm::AesGcm,
+                input_file: input_file.into(),
+                output_file: output_file.into(),
+                key_file: None,
+                key_hex: None,
+                key_base64: None,
+                key_env: None,
+                key_env_format: crate::cli::KeyFormat::Hex,
+                generate_key: false,
+                key_output_file: None,
+                force_key: false,
+                key_format: crate::cli::KeyFormat::Hex,
+                scramble_names: false,
+                preserve: Vec::new(),
+                obfuscate_globals: false,
+                min_obfuscation_size: 0,
+                strip_only: false,
+                show_diff: false,
+                dump_wat: None,
+                embed_key: false,
+                convergent: false,
+                wat: false,
+                envelope: false,
+                notice: None,
+                buffer_size: crate::io::DEFAULT_BUFFER_SIZE,
+                sign_key: None,
+                split_size: None,
+                print_sri: None,
+                keyring_service: None,
+                keyring_account: None,
+            },
+        }
+    }
+}
+
+/// Fluent builder for [`EncryptionConfig`], validated by [`build`](Self::build)
+#[derive(Debug, Clone)]
+pub struct EncryptionConfigBuilder {
+    config: EncryptionConfig,
+}
+
+impl EncryptionConfigBuilder {
+    pub fn algorithm(mut self, algorithm: EncryptionAlgorithm) -> Self {
+        self.config.algorithm = algorithm;
+        self
+    }
+
+    pub fn key_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.config.key_file = Some(path.into());
+        self
+    }
+
+    pub fn key_hex(mut self, key: &str) -> Self {
+        self.config.key_hex = Some(key.to_string());
+        self
+    }
+
+    pub fn key_base64(mut self, key: &str) -> Self {
+        self.config.key_base64 = Some(key.to_string());
+        self
+    }
+
+    /// Read the key from an environment variable, encoded per [`key_env_format`](Self::key_env_format)
+    pub fn key_env(mut self, variable: &str) -> Self {
+        self.config.key_env = Some(variable.to_string());
+        self
+    }
+
+    /// Encoding of the key read from [`key_env`](Self::key_env)
+    pub fn key_env_format(mut self, format: crate::cli::KeyFormat) -> Self {
+        self.config.key_env_format = format;
+        self
+    }
+
+    pub fn keyring(mut self, service: &str, account: &str) -> Self {
+        self.config.keyring_service = Some(service.to_string());
+        self.config.keyring_account = Some(account.to_string());
+        self
+    }
+
+    /// Generate a new key and save it to `path`
+    pub fn generate_key<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.config.generate_key = true;
+        self.config.key_output_file = Some(path.into());
+        self
+    }
+
+    /// Allow overwriting an existing file when saving a generated key
+    pub fn force_key(mut self, enabled: bool) -> Self {
+        self.config.force_key = enabled;
+        self
+    }
+
+    pub fn key_format(mut self, format: crate::cli::KeyFormat) -> Self {
+        self.config.key_format = format;
+        self
+    }
+
+    pub fn scramble_names(mut self, enabled: bool) -> Self {
+        self.config.scramble_names = enabled;
+        self
+    }
+
+    /// Keep this export or debug name (a trailing `*` matches any suffix)
+    pub fn preserve(mut self, name: &str) -> Self {
+        self.config.preserve.push(name.to_string());
+        self
+    }
+
+    pub fn obfuscate_globals(mut self, enabled: bool) -> Self {
+        self.config.obfuscate_globals = enabled;
+        self
+    }
+
+    /// Skip behavior-altering passes for modules smaller than `bytes`
+    pub fn min_obfuscation_size(mut self, bytes: usize) -> Self {
+        self.config.min_obfuscation_size = bytes;
+        self
+    }
+
+    pub fn strip_only(mut self, enabled: bool) -> Self {
+        self.config.strip_only = enabled;
+        self
+    }
+
+    pub fn envelope(mut self, enabled: bool) -> Self {
+        self.config.envelope = enabled;
+        self
+    }
+
+    pub fn notice(mut self, text: &str) -> Self {
+        self.config.notice = Some(text.to_string());
+        self
+    }
+
+    /// Apply a profile's settings; later calls still override them
+    pub fn profile(mut self, profile: Profile) -> Self {
+        let settings = profile.settings();
+        self.config.algorithm = settings.algorithm;
+        self.config.scramble_names = settings.scramble_names;
+        self.config.envelope = settings.envelope;
+        self
+    }
+
+    /// Validate and return the configuration
+    pub fn build(self) -> crate::error::Result<EncryptionConfig> {
+        let config = self.config;
+
+        let sources: Vec<&str> = [
+            ("key_file", config.key_file.is_some()),
+            ("key_hex", config.key_hex.is_some()),
+            ("key_base64", config.key_base64.is_some()),
+            ("key_env", config.key_env.is_some()),
+            ("keyring", config.keyring_service.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(name, set)| set.then_some(name))
+        .collect();
+        if sources.len() > 1 {
+            return Err(crate::error::RusWaCipherError::Config(format!(
+                "Conflicting key sources: {}; set only one",
+                sources.join(", ")
+            )));
+        }
+
+        if config.strip_only && config.scramble_names {
+            return Err(crate::error::RusWaCipherError::Config(
+                "strip_only cannot be combined with scramble_names".to_string(),
+            ));
+        }
+
+        Ok(config)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_algorithm_aliases_match_js_loader() {
+        let loader = include_str!("../web/wasmGuardianLoader.js");
+        let table = loader
+            .split("const aliases = {")
+            .nth(1)
+            .and_then(|rest| rest.split("};").next())
+            .expect("alias table in wasmGuardianLoader.js");
+
+        let mut js: Vec<(String, String)> = table
+            .lines()
+            .filter_map(|line| line.trim().trim_end_matches(',').split_once(':'))
+            .map(|(alias, name)| {
+                (
+                    alias.trim().to_string(),
+                    name.trim().trim_matches('\'').to_string(),
+                )
+            })
+            .collect();
+        js.sort();
+
+        // The loader only distinguishes AES-GCM (any key size) from ChaCha20-Poly1305
+        let mut rust: Vec<(String, String)> = ALGORITHM_ALIASES
+            .iter()
+            .map(|(alias, algorithm)| {
+                let family = match algorithm {
+                    EncryptionAlgorithm::ChaCha20Poly1305 => "chacha20poly1305",
+                    _ => "aes-gcm",
+                };
+                (alias.to_string(), family.to_string())
+            })
+            .collect();
+        rust.sort();
+
+        assert_eq!(js, rust);
+    }
+
     #[test]
     fn test_encryption_algorithm_display() {
         assert_eq!(EncryptionAlgorithm::AesGcm.to_string(), "aes-gcm");
@@ -84,6 +441,25 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_encryption_algorithm_key_size_suffix() {
+        let cases = [
+            ("aes-gcm", EncryptionAlgorithm::AesGcm, 32),
+            ("aes-256-gcm", EncryptionAlgorithm::AesGcm, 32),
+            ("aes-192-gcm", EncryptionAlgorithm::Aes192Gcm, 24),
+            ("aes-128-gcm", EncryptionAlgorithm::Aes128Gcm, 16),
+        ];
+
+        for (name, expected, key_length) in cases {
+            let algorithm = name.parse::<EncryptionAlgorithm>().unwrap();
+            assert_eq!(algorithm, expected);
+            assert_eq!(algorithm.key_length(), key_length);
+        }
+
+        assert_eq!(EncryptionAlgorithm::AesGcm.canonical_name(), "aes-256-gcm");
+        assert!("aes-512-gcm".parse::<EncryptionAlgorithm>().is_err());
+    }
+
     #[test]
     fn test_encryption_algorithm_from_str_case_insensitive() {
         assert!(matches!(
@@ -109,20 +485,12 @@ mod tests {
 
     #[test]
     fn test_encryption_config_creation() {
-        use crate::cli::KeyFormat;
         use std::path::PathBuf;
 
-        let config = EncryptionConfig {
-            algorithm: EncryptionAlgorithm::AesGcm,
-            input_file: PathBuf::from("input.wasm"),
-            output_file: PathBuf::from("output.wasm.enc"),
-            key_file: Some(PathBuf::from("key.txt")),
-            key_hex: None,
-            key_base64: None,
-            generate_key: false,
-            key_output_file: None,
-            key_format: KeyFormat::Hex,
-        };
+        let config = EncryptionConfig::builder("input.wasm", "output.wasm.enc")
+            .key_file("key.txt")
+            .build()
+            .unwrap();
 
         assert!(matches!(config.algorithm, EncryptionAlgorithm::AesGcm));
         assert_eq!(config.input_file, PathBuf::from("input.wasm"));
@@ -138,11 +506,91 @@ mod tests {
         let config = DecryptionConfig {
             input_file: PathBuf::from("input.wasm.enc"),
             output_file: PathBuf::from("output.wasm"),
-            key_file: PathBuf::from("key.txt"),
+            key_file: Some(PathBuf::from("key.txt")),
+            key_env: None,
+            key_env_format: crate::cli::KeyFormat::Hex,
+            keyring_service: None,
+            keyring_account: None,
+            clean: false,
+            buffer_size: crate::io::DEFAULT_BUFFER_SIZE,
+            verify_key: None,
+            insecure: false,
+            parts: false,
         };
 
         assert_eq!(config.input_file, PathBuf::from("input.wasm.enc"));
         assert_eq!(config.output_file, PathBuf::from("output.wasm"));
-        assert_eq!(config.key_file, PathBuf::from("key.txt"));
+        assert_eq!(config.key_file, Some(PathBuf::from("key.txt")));
+    }
+
+    #[test]
+    fn test_builder_valid_config() {
+        let config = EncryptionConfig::builder("in.wasm", "out.wasm")
+            .algorithm(EncryptionAlgorithm::ChaCha20Poly1305)
+            .key_hex("00112233")
+            .scramble_names(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.algorithm, EncryptionAlgorithm::ChaCha20Poly1305);
+        assert_eq!(config.input_file, PathBuf::from("in.wasm"));
+        assert_eq!(config.key_hex.as_deref(), Some("00112233"));
+        assert!(config.scramble_names);
+        assert!(!config.generate_key);
+        assert_eq!(config.buffer_size, crate::io::DEFAULT_BUFFER_SIZE);
+
+        let config = EncryptionConfig::builder("in.wasm", "out.wasm")
+            .generate_key("out.key")
+            .build()
+            .unwrap();
+        assert!(config.generate_key);
+        assert_eq!(config.key_output_file, Some(PathBuf::from("out.key")));
+    }
+
+    #[test]
+    fn test_builder_rejects_conflicting_key_sources() {
+        let err = EncryptionConfig::builder("in.wasm", "out.wasm")
+            .key_hex("00112233")
+            .key_base64("ABEiMw==")
+            .build()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Conflicting key sources: key_hex, key_base64"));
+    }
+
+    #[test]
+    fn test_encryption_algorithm_aliases() {
+        for name in ["AES-GCM", "aes_gcm", "AES256-GCM", "Aes 256 Gcm", "aes-256"] {
+            assert_eq!(
+                name.parse::<EncryptionAlgorithm>().unwrap(),
+                EncryptionAlgorithm::AesGcm,
+                "{}",
+                name
+            );
+        }
+        for name in [
+            "ChaCha20Poly1305",
+            "chacha20-poly1305",
+            "CHACHA20_POLY1305",
+            "chacha20",
+        ] {
+            assert_eq!(
+                name.parse::<EncryptionAlgorithm>().unwrap(),
+                EncryptionAlgorithm::ChaCha20Poly1305,
+                "{}",
+                name
+            );
+        }
+        assert_eq!(
+            "AES_128_GCM".parse::<EncryptionAlgorithm>().unwrap(),
+            EncryptionAlgorithm::Aes128Gcm
+        );
+
+        let err = "blowfish".parse::<EncryptionAlgorithm>().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unknown encryption algorithm: blowfish"));
+        assert!("aes-512-gcm".parse::<EncryptionAlgorithm>().is_err());
     }
 }
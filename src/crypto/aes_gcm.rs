@@ -1,23 +1,54 @@
 use aes_gcm::{
-    aead::{Aead, AeadCore, KeyInit, OsRng},
-    Aes256Gcm, Key, Nonce,
+    aead::{
+        consts::U12,
+        generic_array::GenericArray,
+        stream::{DecryptorBE32, EncryptorBE32},
+        Aead, AeadCore, KeyInit, OsRng,
+    },
+    aes::Aes192,
+    Aes128Gcm, Aes256Gcm, AesGcm, Nonce,
 };
 
-use crate::crypto::traits::{Cipher, EncryptionResult};
+use crate::crypto::traits::{
+    Cipher, EncryptionResult, StreamingCipher, STREAM_NONCE_LENGTH, STREAM_SEGMENT_SIZE,
+};
 use crate::crypto::KeyManager;
 use crate::error::{Result, RusWaCipherError};
 
+type Aes192Gcm = AesGcm<Aes192, U12>;
+
+#[derive(Clone)]
+enum AesGcmVariant {
+    Aes128(Aes128Gcm),
+    Aes192(Aes192Gcm),
+    Aes256(Aes256Gcm),
+}
+
 pub struct AesGcmCipher {
-    cipher: Aes256Gcm,
+    cipher: AesGcmVariant,
 }
 
 impl AesGcmCipher {
-    /// Create a new AES-GCM cipher with the provided key
+    /// Create a new AES-256-GCM cipher with the provided key
     pub fn new(key: &[u8]) -> Result<Self> {
-        KeyManager::validate_key_length(key, 32)?;
+        Self::with_key_size(key, 32)
+    }
+
+    /// Create an AES-GCM cipher for an explicit key size (16, 24 or 32 bytes)
+    pub fn with_key_size(key: &[u8], key_size: usize) -> Result<Self> {
+        KeyManager::validate_key_length(key, key_size)?;
 
-        let key = Key::<Aes256Gcm>::from_slice(key);
-        let cipher = Aes256Gcm::new(key);
+        let cipher = match key_size {
+            16 => AesGcmVariant::Aes128(Aes128Gcm::new(key.into())),
+            24 => AesGcmVariant::Aes192(Aes192Gcm::new(key.into())),
+            32 => AesGcmVariant::Aes256(Aes256Gcm::new(key.into())),
+            other => {
+                return Err(RusWaCipherError::KeyManagement(format!(
+                    "Unsupported AES key size: {} bytes",
+                    other
+                )))
+            }
+        };
 
         Ok(AesGcmCipher { cipher })
     }
@@ -34,11 +65,25 @@ impl Cipher for AesGcmCipher {
     fn encrypt(&self, plaintext: &[u8]) -> Result<EncryptionResult> {
         // Generate a random nonce
         let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        self.encrypt_with_iv(&nonce, plaintext)
+    }
+
+    fn encrypt_with_iv(&self, iv: &[u8], plaintext: &[u8]) -> Result<EncryptionResult> {
+        if iv.len() != 12 {
+            return Err(RusWaCipherError::Encryption(
+                "Invalid IV length for AES-GCM: expected 12 bytes".to_string(),
+            ));
+        }
+
+        let nonce = Nonce::from_slice(iv);
 
         // Encrypt the data
-        let ciphertext = self.cipher.encrypt(&nonce, plaintext).map_err(|e| {
-            RusWaCipherError::Encryption(format!("AES-GCM encryption failed: {:?}", e))
-        })?;
+        let ciphertext = match &self.cipher {
+            AesGcmVariant::Aes128(cipher) => cipher.encrypt(nonce, plaintext),
+            AesGcmVariant::Aes192(cipher) => cipher.encrypt(nonce, plaintext),
+            AesGcmVariant::Aes256(cipher) => cipher.encrypt(nonce, plaintext),
+        }
+        .map_err(|e| RusWaCipherError::Encryption(format!("AES-GCM encryption failed: {:?}", e)))?;
 
         Ok(EncryptionResult {
             iv: nonce.to_vec(),
@@ -52,12 +97,22 @@ impl Cipher for AesGcmCipher {
                 "Invalid IV length for AES-GCM: expected 12 bytes".to_string(),
             ));
         }
+        if ciphertext.len() < self.tag_length() {
+            return Err(RusWaCipherError::Decryption(format!(
+                "ciphertext too short: need at least {} bytes (nonce + tag), got {}",
+                iv.len() + self.tag_length(),
+                iv.len() + ciphertext.len()
+            )));
+        }
 
         let nonce = Nonce::from_slice(iv);
 
-        let plaintext = self.cipher.decrypt(nonce, ciphertext).map_err(|e| {
-            RusWaCipherError::Decryption(format!("AES-GCM decryption failed: {:?}", e))
-        })?;
+        let plaintext = match &self.cipher {
+            AesGcmVariant::Aes128(cipher) => cipher.decrypt(nonce, ciphertext),
+            AesGcmVariant::Aes192(cipher) => cipher.decrypt(nonce, ciphertext),
+            AesGcmVariant::Aes256(cipher) => cipher.decrypt(nonce, ciphertext),
+        }
+        .map_err(|e| RusWaCipherError::Decryption(format!("AES-GCM decryption failed: {:?}", e)))?;
 
         Ok(plaintext)
     }
@@ -66,9 +121,214 @@ impl Cipher for AesGcmCipher {
         12 // AES-GCM standard nonce length
     }
 
+    fn tag_length(&self) -> usize {
+        16 // Full 128-bit GCM tag; truncated tags are not accepted
+    }
+
     fn key_length(&self) -> usize {
-        32 // AES-256 key length
+        match self.cipher {
+            AesGcmVariant::Aes128(_) => 16,
+            AesGcmVariant::Aes192(_) => 24,
+            AesGcmVariant::Aes256(_) => 32,
+        }
+    }
+
+    fn streaming_encryptor(&self) -> Result<Box<dyn StreamingCipher>> {
+        Ok(Box::new(AesGcmStreamEncryptor {
+            cipher: self.cipher.clone(),
+            stream: None,
+            buffer: Vec::new(),
+        }))
+    }
+
+    fn streaming_decryptor(&self) -> Result<Box<dyn StreamingCipher>> {
+        Ok(Box::new(AesGcmStreamDecryptor {
+            cipher: self.cipher.clone(),
+            stream: None,
+            buffer: Vec::new(),
+            segment_length: STREAM_SEGMENT_SIZE + self.tag_length(),
+        }))
+    }
+}
+
+enum AesGcmStream {
+    Aes128(EncryptorBE32<Aes128Gcm>),
+    Aes192(EncryptorBE32<Aes192Gcm>),
+    Aes256(EncryptorBE32<Aes256Gcm>),
+}
+
+impl AesGcmStream {
+    fn encrypt_next(&mut self, segment: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            AesGcmStream::Aes128(stream) => stream.encrypt_next(segment),
+            AesGcmStream::Aes192(stream) => stream.encrypt_next(segment),
+            AesGcmStream::Aes256(stream) => stream.encrypt_next(segment),
+        }
+        .map_err(|e| {
+            RusWaCipherError::Encryption(format!("AES-GCM stream encryption failed: {:?}", e))
+        })
+    }
+
+    fn encrypt_last(self, segment: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            AesGcmStream::Aes128(stream) => stream.encrypt_last(segment),
+            AesGcmStream::Aes192(stream) => stream.encrypt_last(segment),
+            AesGcmStream::Aes256(stream) => stream.encrypt_last(segment),
+        }
+        .map_err(|e| {
+            RusWaCipherError::Encryption(format!("AES-GCM stream encryption failed: {:?}", e))
+        })
+    }
+}
+
+/// Incremental AES-GCM encryptor built on `aead::stream` (STREAM-BE32)
+pub struct AesGcmStreamEncryptor {
+    cipher: AesGcmVariant,
+    stream: Option<AesGcmStream>,
+    buffer: Vec<u8>,
+}
+
+impl StreamingCipher for AesGcmStreamEncryptor {
+    fn init(&mut self, nonce: &[u8]) -> Result<()> {
+        if nonce.len() != STREAM_NONCE_LENGTH {
+            return Err(RusWaCipherError::Encryption(format!(
+                "AES-GCM streaming requires a {}-byte nonce, got {} bytes",
+                STREAM_NONCE_LENGTH,
+                nonce.len()
+            )));
+        }
+
+        let nonce = GenericArray::from_slice(nonce);
+        self.stream = Some(match &self.cipher {
+            AesGcmVariant::Aes128(cipher) => {
+                AesGcmStream::Aes128(EncryptorBE32::from_aead(cipher.clone(), nonce))
+            }
+            AesGcmVariant::Aes192(cipher) => {
+                AesGcmStream::Aes192(EncryptorBE32::from_aead(cipher.clone(), nonce))
+            }
+            AesGcmVariant::Aes256(cipher) => {
+                AesGcmStream::Aes256(EncryptorBE32::from_aead(cipher.clone(), nonce))
+            }
+        });
+        self.buffer.clear();
+        Ok(())
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<Vec<u8>> {
+        let stream = self.stream.as_mut().ok_or_else(stream_not_started)?;
+        self.buffer.extend_from_slice(data);
+
+        // Always hold back the final segment so finalize can seal it as the last one
+        let mut output = Vec::new();
+        while self.buffer.len() > STREAM_SEGMENT_SIZE {
+            let segment: Vec<u8> = self.buffer.drain(..STREAM_SEGMENT_SIZE).collect();
+            output.extend(stream.encrypt_next(&segment)?);
+        }
+        Ok(output)
+    }
+
+    fn finalize(&mut self) -> Result<Vec<u8>> {
+        let stream = self.stream.take().ok_or_else(stream_not_started)?;
+        stream.encrypt_last(&std::mem::take(&mut self.buffer))
+    }
+}
+
+fn stream_not_started() -> RusWaCipherError {
+    RusWaCipherError::Encryption("Stream not initialized: call init first".to_string())
+}
+
+enum AesGcmDecryptStream {
+    Aes128(DecryptorBE32<Aes128Gcm>),
+    Aes192(DecryptorBE32<Aes192Gcm>),
+    Aes256(DecryptorBE32<Aes256Gcm>),
+}
+
+impl AesGcmDecryptStream {
+    fn decrypt_next(&mut self, segment: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            AesGcmDecryptStream::Aes128(stream) => stream.decrypt_next(segment),
+            AesGcmDecryptStream::Aes192(stream) => stream.decrypt_next(segment),
+            AesGcmDecryptStream::Aes256(stream) => stream.decrypt_next(segment),
+        }
+        .map_err(|e| {
+            RusWaCipherError::Decryption(format!("AES-GCM stream decryption failed: {:?}", e))
+        })
+    }
+
+    fn decrypt_last(self, segment: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            AesGcmDecryptStream::Aes128(stream) => stream.decrypt_last(segment),
+            AesGcmDecryptStream::Aes192(stream) => stream.decrypt_last(segment),
+            AesGcmDecryptStream::Aes256(stream) => stream.decrypt_last(segment),
+        }
+        .map_err(|e| {
+            RusWaCipherError::Decryption(format!(
+                "AES-GCM stream decryption failed (tampered or truncated stream): {:?}",
+                e
+            ))
+        })
+    }
+}
+
+/// Incremental decryptor for the output of [`AesGcmStreamEncryptor`]
+pub struct AesGcmStreamDecryptor {
+    cipher: AesGcmVariant,
+    stream: Option<AesGcmDecryptStream>,
+    buffer: Vec<u8>,
+    /// Size of each sealed non-final segment: the plaintext segment plus its tag
+    segment_length: usize,
+}
+
+impl StreamingCipher for AesGcmStreamDecryptor {
+    fn init(&mut self, nonce: &[u8]) -> Result<()> {
+        if nonce.len() != STREAM_NONCE_LENGTH {
+            return Err(RusWaCipherError::Decryption(format!(
+                "AES-GCM streaming requires a {}-byte nonce, got {} bytes",
+                STREAM_NONCE_LENGTH,
+                nonce.len()
+            )));
+        }
+
+        let nonce = GenericArray::from_slice(nonce);
+        self.stream = Some(match &self.cipher {
+            AesGcmVariant::Aes128(cipher) => {
+                AesGcmDecryptStream::Aes128(DecryptorBE32::from_aead(cipher.clone(), nonce))
+            }
+            AesGcmVariant::Aes192(cipher) => {
+                AesGcmDecryptStream::Aes192(DecryptorBE32::from_aead(cipher.clone(), nonce))
+            }
+            AesGcmVariant::Aes256(cipher) => {
+                AesGcmDecryptStream::Aes256(DecryptorBE32::from_aead(cipher.clone(), nonce))
+            }
+        });
+        self.buffer.clear();
+        Ok(())
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<Vec<u8>> {
+        let stream = self
+            .stream
+            .as_mut()
+            .ok_or_else(decrypt_stream_not_started)?;
+        self.buffer.extend_from_slice(data);
+
+        // As when encrypting, the final segment is held back for finalize
+        let mut output = Vec::new();
+        while self.buffer.len() > self.segment_length {
+            let segment: Vec<u8> = self.buffer.drain(..self.segment_length).collect();
+            output.extend(stream.decrypt_next(&segment)?);
+        }
+        Ok(output)
     }
+
+    fn finalize(&mut self) -> Result<Vec<u8>> {
+        let stream = self.stream.take().ok_or_else(decrypt_stream_not_started)?;
+        stream.decrypt_last(&std::mem::take(&mut self.buffer))
+    }
+}
+
+fn decrypt_stream_not_started() -> RusWaCipherError {
+    RusWaCipherError::Decryption("Stream not initialized: call init first".to_string())
 }
 
 #[cfg(test)]
@@ -104,12 +364,114 @@ mod tests {
         assert_eq!(decrypted, plaintext);
     }
 
+    fn stream_encrypt(cipher: &AesGcmCipher, nonce: &[u8], chunks: &[&[u8]]) -> Vec<u8> {
+        let mut stream = cipher.streaming_encryptor().unwrap();
+        stream.init(nonce).unwrap();
+        let mut output = Vec::new();
+        for chunk in chunks {
+            output.extend(stream.update(chunk).unwrap());
+        }
+        output.extend(stream.finalize().unwrap());
+        output
+    }
+
+    fn stream_decrypt(cipher: &AesGcmCipher, nonce: &[u8], chunks: &[&[u8]]) -> Result<Vec<u8>> {
+        let mut stream = cipher.streaming_decryptor()?;
+        stream.init(nonce)?;
+        let mut output = Vec::new();
+        for chunk in chunks {
+            output.extend(stream.update(chunk)?);
+        }
+        output.extend(stream.finalize()?);
+        Ok(output)
+    }
+
+    #[test]
+    fn test_aes_gcm_streaming_round_trip() {
+        let key = KeyManager::generate_key(32).unwrap();
+        let cipher = AesGcmCipher::new(&key).unwrap();
+        let nonce = [7u8; STREAM_NONCE_LENGTH];
+        let plaintext: Vec<u8> = (0..STREAM_SEGMENT_SIZE * 2 + 1000)
+            .map(|i| (i % 251) as u8)
+            .collect();
+
+        let one_shot = stream_encrypt(&cipher, &nonce, &[&plaintext]);
+        let (a, rest) = plaintext.split_at(10);
+        let (b, c) = rest.split_at(STREAM_SEGMENT_SIZE + 5);
+        let pieces = stream_encrypt(&cipher, &nonce, &[a, b, &[], c]);
+        assert_eq!(pieces, one_shot);
+
+        // Three segments, each carrying a 16-byte tag
+        assert_eq!(one_shot.len(), plaintext.len() + 3 * 16);
+
+        // Decrypting must not depend on how the ciphertext is split either
+        let (a, rest) = one_shot.split_at(STREAM_SEGMENT_SIZE + 3);
+        let (b, c) = rest.split_at(STREAM_SEGMENT_SIZE + 29);
+        assert_eq!(
+            stream_decrypt(&cipher, &nonce, &[a, b, c]).unwrap(),
+            plaintext
+        );
+        assert_eq!(
+            stream_decrypt(&cipher, &nonce, &[&one_shot]).unwrap(),
+            plaintext
+        );
+    }
+
+    #[test]
+    fn test_aes_gcm_streaming_rejects_tampered_or_truncated_streams() {
+        let key = KeyManager::generate_key(24).unwrap();
+        let cipher = AesGcmCipher::with_key_size(&key, 24).unwrap();
+        let nonce = [3u8; STREAM_NONCE_LENGTH];
+        let plaintext = vec![0x5Au8; STREAM_SEGMENT_SIZE + 100];
+        let ciphertext = stream_encrypt(&cipher, &nonce, &[&plaintext]);
+
+        let mut tampered = ciphertext.clone();
+        tampered[10] ^= 1;
+        assert!(stream_decrypt(&cipher, &nonce, &[&tampered]).is_err());
+
+        // Dropping the last segment leaves a non-final segment to finalize
+        let truncated = &ciphertext[..STREAM_SEGMENT_SIZE + cipher.tag_length()];
+        assert!(stream_decrypt(&cipher, &nonce, &[truncated]).is_err());
+
+        let empty = stream_encrypt(&cipher, &nonce, &[]);
+        assert_eq!(
+            stream_decrypt(&cipher, &nonce, &[&empty]).unwrap(),
+            Vec::<u8>::new()
+        );
+    }
+
+    #[test]
+    fn test_aes_gcm_streaming_requires_init() {
+        let key = KeyManager::generate_key(16).unwrap();
+        let cipher = AesGcmCipher::with_key_size(&key, 16).unwrap();
+        let mut stream = cipher.streaming_encryptor().unwrap();
+        assert!(stream.update(b"data").is_err());
+        assert!(stream.init(&[0u8; 12]).is_err());
+    }
+
     #[test]
     fn test_invalid_key_length() {
         let short_key = vec![0u8; 16];
         assert!(AesGcmCipher::new(&short_key).is_err());
     }
 
+    #[test]
+    fn test_aes_gcm_key_sizes() {
+        for key_size in [16, 24, 32] {
+            let key = KeyManager::generate_key(key_size).unwrap();
+            let cipher = AesGcmCipher::with_key_size(&key, key_size).unwrap();
+            assert_eq!(cipher.key_length(), key_size);
+
+            let result = cipher.encrypt(b"key size test").unwrap();
+            let decrypted = cipher.decrypt(&result.iv, &result.ciphertext).unwrap();
+            assert_eq!(decrypted, b"key size test");
+        }
+
+        let key = KeyManager::generate_key(16).unwrap();
+        assert!(AesGcmCipher::with_key_size(&key, 32).is_err());
+        assert!(AesGcmCipher::with_key_size(&[0u8; 20], 20).is_err());
+    }
+
     #[test]
     fn test_invalid_iv_length() {
         let key = KeyManager::generate_key(32).unwrap();
@@ -121,6 +483,20 @@ mod tests {
         assert!(cipher.decrypt(&invalid_iv, ciphertext).is_err());
     }
 
+    #[test]
+    fn test_ciphertext_too_short() {
+        for key_size in [16, 24, 32] {
+            let key = KeyManager::generate_key(key_size).unwrap();
+            let cipher = AesGcmCipher::with_key_size(&key, key_size).unwrap();
+
+            // One byte below nonce + tag
+            let err = cipher.decrypt(&[0u8; 12], &[0u8; 15]).unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("ciphertext too short: need at least 28 bytes"));
+        }
+    }
+
     #[test]
     fn test_encryption_result_serialization() {
         let key = KeyManager::generate_key(32).unwrap();
@@ -0,0 +1,199 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::config::EncryptionAlgorithm;
+use crate::error::Result;
+
+/// Receives every [`AuditEvent`] once installed with [`set_audit_sink`]
+pub type AuditSink = Box<dyn Fn(AuditEvent) + Send + Sync>;
+
+/// The installed sink, shared so [`record`] can call it without holding the lock;
+/// a sink that installs or clears sinks itself then cannot deadlock
+type SharedSink = Arc<dyn Fn(AuditEvent) + Send + Sync>;
+
+static AUDIT_SINK: RwLock<Option<SharedSink>> = RwLock::new(None);
+
+/// Kind of cryptographic operation being audited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOperation {
+    Encrypt,
+    Decrypt,
+}
+
+/// One encrypt or decrypt call. Never contains key material or plaintext.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditEvent {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub operation: AuditOperation,
+    /// Canonical algorithm name; `None` if no candidate algorithm could decrypt
+    pub algorithm: Option<String>,
+    /// Hex SHA-256 of the operation's input (plaintext for encrypt, container for decrypt)
+    pub input_sha256: String,
+    pub input_size: usize,
+    pub success: bool,
+}
+
+/// Install `sink` to receive an event for every `encrypt_data`/`decrypt_data` call,
+/// replacing any previous sink
+pub fn set_audit_sink(sink: AuditSink) {
+    *AUDIT_SINK.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::from(sink));
+}
+
+/// Remove the installed audit sink, if any
+pub fn clear_audit_sink() {
+    *AUDIT_SINK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// A sink appending each event as one JSON line to `path`
+pub fn jsonl_file_sink<P: AsRef<Path>>(path: P) -> Result<AuditSink> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let file: Mutex<File> = Mutex::new(file);
+
+    Ok(Box::new(move |event| {
+        let Ok(line) = serde_json::to_string(&event) else {
+            return;
+        };
+        let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = writeln!(file, "{}", line) {
+            log::warn!("Failed to write audit event: {}", e);
+        }
+    }))
+}
+
+/// Report an operation to the installed sink; a no-op when none is installed
+pub(crate) fn record(
+    operation: AuditOperation,
+    algorithm: Option<&EncryptionAlgorithm>,
+    input: &[u8],
+    success: bool,
+) {
+    let sink = AUDIT_SINK.read().unwrap_or_else(|e| e.into_inner()).clone();
+    let Some(sink) = sink else {
+        return;
+    };
+
+    sink(AuditEvent {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        operation,
+        algorithm: algorithm.map(|a| a.canonical_name().to_string()),
+        input_sha256: hex::encode(Sha256::digest(input)),
+        input_size: input.len(),
+        success,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{decrypt_data, encrypt_data, generate_key};
+    use serial_test::serial;
+
+    /// Install a sink collecting every event; other tests may add events too
+    fn capture() -> Arc<Mutex<Vec<AuditEvent>>> {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink_events = Arc::clone(&events);
+        set_audit_sink(Box::new(move |event| {
+            sink_events.lock().unwrap().push(event)
+        }));
+        events
+    }
+
+    fn fingerprint(data: &[u8]) -> String {
+        hex::encode(Sha256::digest(data))
+    }
+
+    #[test]
+    #[serial]
+    fn test_audit_event_per_operation() {
+        let events = capture();
+        let algorithm = EncryptionAlgorithm::ChaCha20Poly1305;
+        let key = generate_key(&algorithm).unwrap();
+        let plaintext = b"audited module bytes for test_audit_event_per_operation";
+
+        let container = encrypt_data(plaintext, &key, &algorithm).unwrap();
+        decrypt_data(&container, &key).unwrap();
+        let mut tampered = container.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(decrypt_data(&tampered, &key).is_err());
+        clear_audit_sink();
+
+        let ours = [
+            fingerprint(plaintext),
+            fingerprint(&container),
+            fingerprint(&tampered),
+        ];
+        let events: Vec<_> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| ours.contains(&e.input_sha256))
+            .cloned()
+            .collect();
+        assert_eq!(events.len(), 3);
+
+        assert_eq!(events[0].operation, AuditOperation::Encrypt);
+        assert_eq!(events[0].algorithm.as_deref(), Some("chacha20poly1305"));
+        assert_eq!(events[0].input_size, plaintext.len());
+        assert!(events[0].success);
+
+        assert_eq!(events[1].operation, AuditOperation::Decrypt);
+        assert_eq!(events[1].algorithm.as_deref(), Some("chacha20poly1305"));
+        assert!(events[1].success);
+
+        assert_eq!(events[2].operation, AuditOperation::Decrypt);
+        assert_eq!(events[2].algorithm, None);
+        assert!(!events[2].success);
+    }
+
+    #[test]
+    #[serial]
+    fn test_sink_can_clear_itself() {
+        let calls = Arc::new(Mutex::new(0));
+        let sink_calls = Arc::clone(&calls);
+        set_audit_sink(Box::new(move |_| {
+            *sink_calls.lock().unwrap() += 1;
+            clear_audit_sink();
+        }));
+
+        let algorithm = EncryptionAlgorithm::AesGcm;
+        let key = generate_key(&algorithm).unwrap();
+        encrypt_data(b"module", &key, &algorithm).unwrap();
+        encrypt_data(b"module", &key, &algorithm).unwrap();
+        assert_eq!(*calls.lock().unwrap(), 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_jsonl_file_sink() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        set_audit_sink(jsonl_file_sink(&path).unwrap());
+
+        let algorithm = EncryptionAlgorithm::AesGcm;
+        let key = generate_key(&algorithm).unwrap();
+        let plaintext = b"audited module bytes for test_jsonl_file_sink";
+        encrypt_data(plaintext, &key, &algorithm).unwrap();
+        clear_audit_sink();
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let line = log
+            .lines()
+            .find(|line| line.contains(&fingerprint(plaintext)))
+            .unwrap();
+        let event: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert_eq!(event["operation"], "encrypt");
+        assert_eq!(event["algorithm"], "aes-256-gcm");
+        assert_eq!(event["success"], true);
+        assert!(!log.contains(&hex::encode(&key)));
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::EncryptionAlgorithm;
+use crate::crypto::{decrypt_data, encrypt_data};
+use crate::error::{Result, RusWaCipherError};
+use crate::io::{read_file, write_file};
+use crate::wasm::WasmParser;
+
+/// Marker at the start of a decrypted bundle
+pub const BUNDLE_MAGIC: &[u8; 4] = b"RWCB";
+
+/// Where one module lives inside a bundle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleEntry {
+    /// File name the module is extracted to
+    pub name: String,
+    /// Offset of the module bytes after the manifest
+    pub offset: usize,
+    pub size: usize,
+}
+
+/// Encrypt several modules into one container under a shared key.
+///
+/// The plaintext is `[magic][manifest length: u32 LE][JSON manifest][modules]`,
+/// encrypted as a single AEAD message so the manifest is authenticated too.
+/// Modules are named after their input file names, which must be unique.
+pub fn encrypt_bundle(
+    inputs: &[&Path],
+    key: &[u8],
+    algorithm: &EncryptionAlgorithm,
+    output: &Path,
+) -> Result<Vec<BundleEntry>> {
+    let mut manifest = Vec::with_capacity(inputs.len());
+    let mut modules = Vec::new();
+    for input in inputs {
+        let name = input
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| {
+                RusWaCipherError::InvalidInput(format!("{:?} has no usable file name", input))
+            })?
+            .to_string();
+        if manifest
+            .iter()
+            .any(|entry: &BundleEntry| entry.name == name)
+        {
+            return Err(RusWaCipherError::InvalidInput(format!(
+                "Duplicate module name '{}' in bundle",
+                name
+            )));
+        }
+
+        let module = read_file(input)?;
+        WasmParser::validate_wasm(&module)?;
+        manifest.push(BundleEntry {
+            name,
+            offset: modules.len(),
+            size: module.len(),
+        });
+        modules.extend_from_slice(&module);
+    }
+
+    let manifest_json = serde_json::to_vec(&manifest)
+        .map_err(|e| RusWaCipherError::Encryption(format!("Cannot encode manifest: {}", e)))?;
+    let mut plaintext = Vec::with_capacity(8 + manifest_json.len() + modules.len());
+    plaintext.extend_from_slice(BUNDLE_MAGIC);
+    plaintext.extend_from_slice(
+        &crate::wasm::leb::checked_length(manifest_json.len(), "Bundle manifest")?.to_le_bytes(),
+    );
+    plaintext.extend_from_slice(&manifest_json);
+    plaintext.extend_from_slice(&modules);
+
+    write_file(output, &encrypt_data(&plaintext, key, algorithm)?)?;
+    Ok(manifest)
+}
+
+/// Decrypt a bundle made by [`encrypt_bundle`] and extract every module into `output_dir`.
+///
+/// Each module is validated before anything is written.
+pub fn decrypt_bundle(input: &Path, key: &[u8], output_dir: &Path) -> Result<Vec<BundleEntry>> {
+    let plaintext = decrypt_data(&read_file(input)?, key)?;
+    let (manifest, modules) = split_manifest(&plaintext)?;
+
+    let mut names = HashSet::new();
+    let mut extracted = Vec::with_capacity(manifest.len());
+    for entry in &manifest {
+        // Names come from the authenticated manifest, but never leave output_dir
+        if Path::new(&entry.name).file_name().and_then(|n| n.to_str()) != Some(&entry.name)
+            || !names.insert(&entry.name)
+        {
+            return Err(RusWaCipherError::InvalidInput(format!(
+                "Invalid module name '{}' in bundle manifest",
+                entry.name
+            )));
+        }
+
+        let module = entry
+            .offset
+            .checked_add(entry.size)
+            .and_then(|end| modules.get(entry.offset..end))
+            .ok_or_else(|| {
+                RusWaCipherError::InvalidInput(format!(
+                    "Module '{}' lies outside the bundle",
+                    entry.name
+                ))
+            })?;
+        WasmParser::validate_wasm(module).map_err(|e| {
+            RusWaCipherError::InvalidInput(format!(
+                "Module '{}' in bundle is not valid WASM: {}",
+                entry.name, e
+            ))
+        })?;
+        extracted.push(module);
+    }
+
+    // Modules must tile the data after the manifest exactly: no overlaps, no stray bytes
+    let mut ranges: Vec<_> = manifest
+        .iter()
+        .map(|e| (e.offset, e.size, &e.name))
+        .collect();
+    ranges.sort_unstable();
+    let mut covered = 0;
+    for (offset, size, name) in ranges {
+        if offset != covered {
+            return Err(RusWaCipherError::InvalidInput(format!(
+                "Module '{}' {} the previous module in the bundle",
+                name,
+                if offset < covered {
+                    "overlaps"
+                } else {
+                    "is not adjacent to"
+                }
+            )));
+        }
+        covered += size;
+    }
+    if covered != modules.len() {
+        return Err(RusWaCipherError::InvalidInput(format!(
+            "Bundle has {} bytes not referenced by its manifest",
+            modules.len() - covered
+        )));
+    }
+
+    std::fs::create_dir_all(output_dir)?;
+    for (entry, module) in manifest.iter().zip(extracted) {
+        write_file(output_dir.join(&entry.name), module)?;
+    }
+    Ok(manifest)
+}
+
+fn split_manifest(plaintext: &[u8]) -> Result<(Vec<BundleEntry>, &[u8])> {
+    let not_a_bundle = || RusWaCipherError::InvalidInput("Decrypted data is not a bundle".into());
+    if plaintext.len() < 8 || !plaintext.starts_with(BUNDLE_MAGIC) {
+        return Err(not_a_bundle());
+    }
+
+    let length = u32::from_le_bytes(plaintext[4..8].try_into().unwrap()) as usize;
+    let end = 8usize.checked_add(length).ok_or_else(not_a_bundle)?;
+    let manifest = plaintext.get(8..end).ok_or_else(not_a_bundle)?;
+    let manifest = serde_json::from_slice(manifest)
+        .map_err(|e| RusWaCipherError::InvalidInput(format!("Invalid bundle manifest: {}", e)))?;
+    Ok((manifest, &plaintext[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_key;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let first = dir.path().join("math.wasm");
+        let second = dir.path().join("greet.wasm");
+        let math =
+            wat::parse_str(r#"(module (func (export "add") (result i32) i32.const 3))"#).unwrap();
+        let greet = wat::parse_str(r#"(module (memory (export "memory") 1))"#).unwrap();
+        std::fs::write(&first, &math).unwrap();
+        std::fs::write(&second, &greet).unwrap();
+
+        let algorithm = EncryptionAlgorithm::AesGcm;
+        let key = generate_key(&algorithm).unwrap();
+        let bundle = dir.path().join("bundle.enc");
+        let manifest = encrypt_bundle(&[&first, &second], &key, &algorithm, &bundle).unwrap();
+        assert_eq!(manifest[1].offset, math.len());
+
+        let out = dir.path().join("out");
+        let extracted = decrypt_bundle(&bundle, &key, &out).unwrap();
+        assert_eq!(extracted, manifest);
+        assert_eq!(std::fs::read(out.join("math.wasm")).unwrap(), math);
+        assert_eq!(std::fs::read(out.join("greet.wasm")).unwrap(), greet);
+    }
+
+    #[test]
+    fn test_bundle_rejects_duplicate_names() {
+        let dir = TempDir::new().unwrap();
+        let module = dir.path().join("app.wasm");
+        std::fs::write(&module, wat::parse_str("(module)").unwrap()).unwrap();
+
+        let algorithm = EncryptionAlgorithm::ChaCha20Poly1305;
+        let key = generate_key(&algorithm).unwrap();
+        let err = encrypt_bundle(&[&module, &module], &key, &algorithm, &dir.path().join("b"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Duplicate module name 'app.wasm'"));
+    }
+
+    /// Encrypt a hand-built bundle with an arbitrary manifest and try to extract it
+    fn decrypt_crafted_bundle(dir: &Path, manifest: &[BundleEntry], modules: &[u8]) -> Result<()> {
+        let manifest = serde_json::to_vec(manifest).unwrap();
+        let mut plaintext = BUNDLE_MAGIC.to_vec();
+        plaintext.extend_from_slice(&(manifest.len() as u32).to_le_bytes());
+        plaintext.extend_from_slice(&manifest);
+        plaintext.extend_from_slice(modules);
+
+        let algorithm = EncryptionAlgorithm::AesGcm;
+        let key = generate_key(&algorithm).unwrap();
+        let bundle = dir.join("bundle.enc");
+        std::fs::write(&bundle, encrypt_data(&plaintext, &key, &algorithm).unwrap()).unwrap();
+        decrypt_bundle(&bundle, &key, &dir.join("out")).map(|_| ())
+    }
+
+    fn entry(name: &str, offset: usize, size: usize) -> BundleEntry {
+        BundleEntry {
+            name: name.to_string(),
+            offset,
+            size,
+        }
+    }
+
+    #[test]
+    fn test_decrypt_bundle_rejects_escaping_names() {
+        let dir = TempDir::new().unwrap();
+        let module = wat::parse_str("(module)").unwrap();
+        let manifest = [entry("../evil.wasm", 0, module.len())];
+
+        let err = decrypt_crafted_bundle(dir.path(), &manifest, &module)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Invalid module name '../evil.wasm'"));
+        assert!(!dir.path().join("evil.wasm").exists());
+    }
+
+    #[test]
+    fn test_decrypt_bundle_rejects_invalid_module() {
+        let dir = TempDir::new().unwrap();
+        let mut modules = wat::parse_str("(module)").unwrap();
+        modules.extend_from_slice(b"\0asm\xff\xff\xff\xff");
+        let manifest = [entry("good.wasm", 0, 8), entry("bad.wasm", 8, 8)];
+
+        let err = decrypt_crafted_bundle(dir.path(), &manifest, &modules)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Module 'bad.wasm' in bundle is not valid WASM"),
+            "{}",
+            err
+        );
+        assert!(!dir.path().join("out").join("good.wasm").exists());
+    }
+
+    #[test]
+    fn test_decrypt_bundle_rejects_overlapping_or_unreferenced_bytes() {
+        let dir = TempDir::new().unwrap();
+        let module = wat::parse_str("(module)").unwrap();
+
+        let overlapping = [
+            entry("a.wasm", 0, module.len()),
+            entry("b.wasm", 0, module.len()),
+        ];
+        let err = decrypt_crafted_bundle(dir.path(), &overlapping, &module)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Module 'b.wasm' overlaps"), "{}", err);
+
+        let mut padded = module.clone();
+        padded.extend_from_slice(b"hidden");
+        let err = decrypt_crafted_bundle(dir.path(), &[entry("a.wasm", 0, module.len())], &padded)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("6 bytes not referenced"), "{}", err);
+    }
+}
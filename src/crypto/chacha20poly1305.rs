@@ -34,9 +34,21 @@ impl Cipher for ChaCha20Poly1305Cipher {
     fn encrypt(&self, plaintext: &[u8]) -> Result<EncryptionResult> {
         // Generate a random nonce
         let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        self.encrypt_with_iv(&nonce, plaintext)
+    }
+
+    fn encrypt_with_iv(&self, iv: &[u8], plaintext: &[u8]) -> Result<EncryptionResult> {
+        if iv.len() != 12 {
+            return Err(RusWaCipherError::Encryption(format!(
+                "ChaCha20-Poly1305 requires a 12-byte nonce, got {} bytes",
+                iv.len()
+            )));
+        }
+
+        let nonce = Nonce::from_slice(iv);
 
         // Encrypt the data
-        let ciphertext = self.cipher.encrypt(&nonce, plaintext).map_err(|e| {
+        let ciphertext = self.cipher.encrypt(nonce, plaintext).map_err(|e| {
             RusWaCipherError::Encryption(format!("ChaCha20-Poly1305 encryption failed: {:?}", e))
         })?;
 
@@ -53,6 +65,13 @@ impl Cipher for ChaCha20Poly1305Cipher {
                 iv.len()
             )));
         }
+        if ciphertext.len() < self.tag_length() {
+            return Err(RusWaCipherError::Decryption(format!(
+                "ciphertext too short: need at least {} bytes (nonce + tag), got {}",
+                iv.len() + self.tag_length(),
+                iv.len() + ciphertext.len()
+            )));
+        }
 
         let nonce = Nonce::from_slice(iv);
 
@@ -67,6 +86,10 @@ impl Cipher for ChaCha20Poly1305Cipher {
         12 // ChaCha20-Poly1305 uses 12-byte nonces
     }
 
+    fn tag_length(&self) -> usize {
+        16 // Poly1305 tag
+    }
+
     fn key_length(&self) -> usize {
         32 // ChaCha20-Poly1305 uses 32-byte keys
     }
@@ -77,6 +100,14 @@ mod tests {
     use super::*;
     use crate::crypto::key_management::KeyManager;
 
+    #[test]
+    fn test_streaming_not_supported() {
+        let key = KeyManager::generate_key(32).unwrap();
+        let cipher = ChaCha20Poly1305Cipher::new(&key).unwrap();
+        assert!(cipher.streaming_encryptor().is_err());
+        assert!(cipher.streaming_decryptor().is_err());
+    }
+
     #[test]
     fn test_chacha20poly1305_encrypt_decrypt() {
         let key = KeyManager::generate_key(32).unwrap();
@@ -114,6 +145,18 @@ mod tests {
         assert!(cipher.decrypt(&invalid_nonce, ciphertext).is_err());
     }
 
+    #[test]
+    fn test_chacha20poly1305_ciphertext_too_short() {
+        let key = KeyManager::generate_key(32).unwrap();
+        let cipher = ChaCha20Poly1305Cipher::new(&key).unwrap();
+
+        // One byte below nonce + tag
+        let err = cipher.decrypt(&[0u8; 12], &[0u8; 15]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("ciphertext too short: need at least 28 bytes"));
+    }
+
     #[test]
     fn test_chacha20poly1305_different_keys_different_results() {
         let key1 = KeyManager::generate_key(32).unwrap();
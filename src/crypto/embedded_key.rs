@@ -0,0 +1,102 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::error::{Result, RusWaCipherError};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Marker that terminates an embedded-key trailer
+pub const EMBEDDED_KEY_MAGIC: &[u8; 8] = b"RWCEMBKY";
+
+const TAG_LENGTH: usize = 32;
+const FOOTER_LENGTH: usize = TAG_LENGTH + 4 + EMBEDDED_KEY_MAGIC.len();
+
+/// Append the key to an encrypted container.
+///
+/// Layout: `[container][key][HMAC-SHA256(key, container || key)][key length: u32 LE][magic]`.
+/// This makes the file self-decrypting and offers no confidentiality on its own;
+/// key secrecy must be provided by the transport. The HMAC is keyed with the
+/// embedded key itself, so anyone holding the file can recompute it: it detects
+/// corruption, not tampering.
+pub fn append_key_trailer(container: &[u8], key: &[u8]) -> Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(container.len() + key.len() + FOOTER_LENGTH);
+    output.extend_from_slice(container);
+    output.extend_from_slice(key);
+
+    let tag = trailer_tag(key, &output)?;
+    output.extend_from_slice(&tag);
+    output.extend_from_slice(&(key.len() as u32).to_le_bytes());
+    output.extend_from_slice(EMBEDDED_KEY_MAGIC);
+
+    Ok(output)
+}
+
+/// Split an embedded-key trailer off the data.
+///
+/// Returns `None` if the data carries no trailer, otherwise the container
+/// and the embedded key once its checksum matches.
+pub fn split_key_trailer(data: &[u8]) -> Result<Option<(&[u8], Vec<u8>)>> {
+    if data.len() < FOOTER_LENGTH || !data.ends_with(EMBEDDED_KEY_MAGIC) {
+        return Ok(None);
+    }
+
+    let length_start = data.len() - EMBEDDED_KEY_MAGIC.len() - 4;
+    let key_length = u32::from_le_bytes([
+        data[length_start],
+        data[length_start + 1],
+        data[length_start + 2],
+        data[length_start + 3],
+    ]) as usize;
+
+    let tag_start = length_start - TAG_LENGTH;
+    let key_start = tag_start.checked_sub(key_length).ok_or_else(|| {
+        RusWaCipherError::Decryption("Embedded key trailer is truncated".to_string())
+    })?;
+
+    let key = &data[key_start..tag_start];
+    let expected_tag = trailer_tag(key, &data[..tag_start])?;
+    if expected_tag != data[tag_start..length_start] {
+        return Err(RusWaCipherError::Decryption(
+            "Embedded key trailer checksum mismatch; the file is corrupted".to_string(),
+        ));
+    }
+
+    Ok(Some((&data[..key_start], key.to_vec())))
+}
+
+fn trailer_tag(key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    let mut mac = HmacSha256::new_from_slice(key)
+        .map_err(|e| RusWaCipherError::KeyManagement(format!("Invalid HMAC key: {}", e)))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_trailer_round_trip() {
+        let container = b"nonce and ciphertext";
+        let key = vec![0x42u8; 32];
+
+        let data = append_key_trailer(container, &key).unwrap();
+        let (extracted, extracted_key) = split_key_trailer(&data).unwrap().unwrap();
+        assert_eq!(extracted, container);
+        assert_eq!(extracted_key, key);
+    }
+
+    #[test]
+    fn test_no_trailer() {
+        assert!(split_key_trailer(b"plain container data without trailer")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_corrupted_trailer_rejected() {
+        let mut data = append_key_trailer(b"container", &[0x42u8; 32]).unwrap();
+        data[0] ^= 0xFF;
+        assert!(split_key_trailer(&data).is_err());
+    }
+}
@@ -0,0 +1,605 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use log::info;
+use sha2::Sha256;
+
+use crate::config::EncryptionAlgorithm;
+use crate::crypto::audit::{self, AuditOperation};
+use crate::crypto::{AesGcmCipher, ChaCha20Poly1305Cipher, Cipher, EncryptionResult, KeyManager};
+use crate::error::{Result, RusWaCipherError};
+use crate::io::{read_chunked, read_file, read_key_file, write_file_atomic, DEFAULT_BUFFER_SIZE};
+
+/// Create a cipher for the given algorithm, validating the key length first
+pub fn create_cipher(algorithm: &EncryptionAlgorithm, key: &[u8]) -> Result<Box<dyn Cipher>> {
+    KeyManager::validate_key_for_algorithm(key, algorithm)?;
+
+    Ok(match algorithm {
+        EncryptionAlgorithm::AesGcm
+        | EncryptionAlgorithm::Aes128Gcm
+        | EncryptionAlgorithm::Aes192Gcm => {
+            Box::new(AesGcmCipher::with_key_size(key, algorithm.key_length())?)
+        }
+        EncryptionAlgorithm::ChaCha20Poly1305 => Box::new(ChaCha20Poly1305Cipher::new(key)?),
+    })
+}
+
+/// Encrypt data and serialize it as IV + ciphertext
+pub fn encrypt_data(data: &[u8], key: &[u8], algorithm: &EncryptionAlgorithm) -> Result<Vec<u8>> {
+    let result = create_cipher(algorithm, key).and_then(|cipher| {
+        info!("Encrypting with {}...", algorithm.canonical_name());
+        Ok(cipher.encrypt(data)?.serialize())
+    });
+    audit::record(
+        AuditOperation::Encrypt,
+        Some(algorithm),
+        data,
+        result.is_ok(),
+    );
+    result
+}
+
+/// Encrypt data with an IV derived from the plaintext (convergent encryption).
+///
+/// The IV is `HMAC-SHA256(k, plaintext)[..12]`, where `k` is derived from the key
+/// with HKDF-SHA256 under its own label so the AEAD key is never reused as a MAC
+/// key. Identical plaintext and key
+/// always produce identical output. This lets a CDN deduplicate modules, but it
+/// also reveals when two encrypted files hold the same module.
+pub fn encrypt_data_convergent(
+    data: &[u8],
+    key: &[u8],
+    algorithm: &EncryptionAlgorithm,
+) -> Result<Vec<u8>> {
+    let result = create_cipher(algorithm, key).and_then(|cipher| {
+        info!(
+            "Encrypting with {} (convergent IV)...",
+            algorithm.canonical_name()
+        );
+        let iv = convergent_iv(key, data, cipher.iv_length())?;
+        Ok(cipher.encrypt_with_iv(&iv, data)?.serialize())
+    });
+    audit::record(
+        AuditOperation::Encrypt,
+        Some(algorithm),
+        data,
+        result.is_ok(),
+    );
+    result
+}
+
+/// HKDF info label for the convergent IV subkey
+const CONVERGENT_IV_LABEL: &[u8] = b"ruswacipher convergent-iv v1";
+
+fn convergent_iv(key: &[u8], data: &[u8], iv_length: usize) -> Result<Vec<u8>> {
+    let mut subkey = [0u8; 32];
+    Hkdf::<Sha256>::new(None, key)
+        .expand(CONVERGENT_IV_LABEL, &mut subkey)
+        .map_err(|e| RusWaCipherError::KeyManagement(format!("Cannot derive IV key: {}", e)))?;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(&subkey)
+        .map_err(|e| RusWaCipherError::KeyManagement(format!("Invalid HMAC key: {}", e)))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes()[..iv_length].to_vec())
+}
+
+/// Algorithms that accept a key of the given length, in the order they are tried
+pub fn candidate_algorithms(key_length: usize) -> Vec<EncryptionAlgorithm> {
+    [
+        EncryptionAlgorithm::AesGcm,
+        EncryptionAlgorithm::ChaCha20Poly1305,
+        EncryptionAlgorithm::Aes192Gcm,
+        EncryptionAlgorithm::Aes128Gcm,
+    ]
+    .into_iter()
+    .filter(|algorithm| algorithm.key_length() == key_length)
+    .collect()
+}
+
+/// Encrypt and decrypt speed of one algorithm, in MB/s (10^6 bytes per second)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Throughput {
+    pub encrypt_mb_per_s: f64,
+    pub decrypt_mb_per_s: f64,
+}
+
+/// Measure every built-in algorithm on this host with a `size`-byte buffer,
+/// keyed by canonical algorithm name.
+///
+/// Key generation and cipher setup happen before the clock starts, so only
+/// the AEAD work itself is timed.
+pub fn benchmark_algorithms(size: usize) -> Result<BTreeMap<&'static str, Throughput>> {
+    if size == 0 {
+        return Err(RusWaCipherError::InvalidInput(
+            "Benchmark size must be greater than zero".to_string(),
+        ));
+    }
+
+    let data = vec![0u8; size];
+    let mut results = BTreeMap::new();
+    for algorithm in [
+        EncryptionAlgorithm::AesGcm,
+        EncryptionAlgorithm::Aes192Gcm,
+        EncryptionAlgorithm::Aes128Gcm,
+        EncryptionAlgorithm::ChaCha20Poly1305,
+    ] {
+        let cipher = create_cipher(
+            &algorithm,
+            &KeyManager::generate_key(algorithm.key_length())?,
+        )?;
+
+        let start = Instant::now();
+        let encrypted = cipher.encrypt(&data)?;
+        let encrypt = start.elapsed();
+
+        let start = Instant::now();
+        cipher.decrypt(&encrypted.iv, &encrypted.ciphertext)?;
+        let decrypt = start.elapsed();
+
+        results.insert(
+            algorithm.canonical_name(),
+            Throughput {
+                encrypt_mb_per_s: mb_per_s(size, encrypt),
+                decrypt_mb_per_s: mb_per_s(size, decrypt),
+            },
+        );
+    }
+    Ok(results)
+}
+
+fn mb_per_s(bytes: usize, elapsed: Duration) -> f64 {
+    // Clamp so a coarse clock reading zero still yields a finite rate
+    bytes as f64 / 1e6 / elapsed.as_secs_f64().max(1e-9)
+}
+
+/// Time spent in each phase of a decryption
+#[derive(Debug, Clone)]
+pub struct DecryptMetrics {
+    /// Algorithm that decrypted the data
+    pub algorithm: EncryptionAlgorithm,
+    /// Splitting the container into IV and ciphertext
+    pub parse: Duration,
+    /// Key derivation (zero: keys are used as-is)
+    pub kdf: Duration,
+    /// AEAD decryption, including failed attempts with other candidate algorithms
+    pub decrypt: Duration,
+    pub total: Duration,
+}
+
+/// Decrypt IV + ciphertext data, trying each algorithm compatible with the key
+pub fn decrypt_data(data: &[u8], key: &[u8]) -> Result<Vec<u8>> {
+    decrypt_data_timed(data, key).map(|(plaintext, _)| plaintext)
+}
+
+/// Decrypt like [`decrypt_data`], also reporting how long each phase took
+pub fn decrypt_data_timed(data: &[u8], key: &[u8]) -> Result<(Vec<u8>, DecryptMetrics)> {
+    let result = decrypt_with_candidates(data, key);
+    audit::record(
+        AuditOperation::Decrypt,
+        result.as_ref().ok().map(|(_, metrics)| &metrics.algorithm),
+        data,
+        result.is_ok(),
+    );
+    result
+}
+
+fn decrypt_with_candidates(data: &[u8], key: &[u8]) -> Result<(Vec<u8>, DecryptMetrics)> {
+    let start = Instant::now();
+    let mut parse = Duration::ZERO;
+    let mut decrypt = Duration::ZERO;
+
+    let candidates = candidate_algorithms(key.len());
+    if candidates.is_empty() {
+        return Err(RusWaCipherError::KeyManagement(format!(
+            "No supported algorithm uses a {}-byte key",
+            key.len()
+        )));
+    }
+
+    let tried: Vec<_> = candidates.iter().map(|a| a.canonical_name()).collect();
+    let ciphers = candidates
+        .iter()
+        .map(|algorithm| create_cipher(algorithm, key))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Every algorithm stores an IV and appends a full tag, so a container
+    // shorter than the smallest IV + tag was truncated
+    let minimum = ciphers
+        .iter()
+        .map(|cipher| cipher.iv_length() + cipher.tag_length())
+        .min()
+        .unwrap_or(0);
+    if data.len() < minimum {
+        return Err(RusWaCipherError::Decryption(format!(
+            "truncated authentication tag (container too short: need at least {} bytes, got {})",
+            minimum,
+            data.len()
+        )));
+    }
+
+    let mut last_error = None;
+    for (algorithm, cipher) in candidates.into_iter().zip(ciphers) {
+        if data.len() < cipher.iv_length() + cipher.tag_length() {
+            continue;
+        }
+        info!(
+            "Attempting decryption with {}...",
+            algorithm.canonical_name()
+        );
+
+        let phase = Instant::now();
+        let encryption_result = EncryptionResult::deserialize(data, cipher.iv_length())?;
+        parse += phase.elapsed();
+
+        let phase = Instant::now();
+        let result = cipher.decrypt(&encryption_result.iv, &encryption_result.ciphertext);
+        decrypt += phase.elapsed();
+
+        match result {
+            Ok(plaintext) => {
+                info!("Successfully decrypted with {}", algorithm.canonical_name());
+                let metrics = DecryptMetrics {
+                    algorithm,
+                    parse,
+                    kdf: Duration::ZERO,
+                    decrypt,
+                    total: start.elapsed(),
+                };
+                return Ok((plaintext, metrics));
+            }
+            Err(e) => {
+                info!("{} decryption failed", algorithm.canonical_name());
+                last_error = Some(e);
+            }
+        }
+    }
+
+    let last_error = last_error.expect("at least one candidate algorithm was tried");
+    Err(RusWaCipherError::Authentication(format!(
+        "no algorithm that uses a {}-byte key ({}) could decrypt the data (last error: {}); \
+         check that the key is the one used for encryption and that the file is not corrupted",
+        key.len(),
+        tried.join(", "),
+        last_error
+    )))
+}
+
+/// Encrypt everything read from `reader` and write the IV + ciphertext to `writer`.
+///
+/// The whole input is buffered, since the container holds a single AEAD message.
+pub fn encrypt_stream<R: Read, W: Write>(
+    reader: R,
+    writer: W,
+    key: &[u8],
+    algorithm: &EncryptionAlgorithm,
+) -> Result<()> {
+    encrypt_stream_with_progress(reader, writer, key, algorithm, DEFAULT_BUFFER_SIZE, |_| {})
+}
+
+/// Like [`encrypt_stream`], reading in `buffer_size` chunks and calling `progress`
+/// with the number of input bytes read so far
+pub fn encrypt_stream_with_progress<R: Read, W: Write, F: FnMut(u64)>(
+    reader: R,
+    mut writer: W,
+    key: &[u8],
+    algorithm: &EncryptionAlgorithm,
+    buffer_size: usize,
+    progress: F,
+) -> Result<()> {
+    let data = read_chunked(reader, buffer_size, progress)?;
+    writer.write_all(&encrypt_data(&data, key, algorithm)?)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Decrypt an IV + ciphertext container read from `reader` and write the plaintext to `writer`
+pub fn decrypt_stream<R: Read, W: Write>(reader: R, writer: W, key: &[u8]) -> Result<()> {
+    decrypt_stream_with_progress(reader, writer, key, DEFAULT_BUFFER_SIZE, |_| {})
+}
+
+/// Like [`decrypt_stream`], reading in `buffer_size` chunks and calling `progress`
+/// with the number of input bytes read so far
+pub fn decrypt_stream_with_progress<R: Read, W: Write, F: FnMut(u64)>(
+    reader: R,
+    mut writer: W,
+    key: &[u8],
+    buffer_size: usize,
+    progress: F,
+) -> Result<()> {
+    let data = read_chunked(reader, buffer_size, progress)?;
+    writer.write_all(&decrypt_data(&data, key)?)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Encrypt a file with a key file and write the container to `output`
+pub fn encrypt_file<P: AsRef<Path>, Q: AsRef<Path>, K: AsRef<Path>>(
+    input: P,
+    output: Q,
+    key_file: K,
+    algorithm: &EncryptionAlgorithm,
+) -> Result<()> {
+    let key = read_key_file(key_file)?;
+    // Encrypt fully before touching `output`, which may be the input itself
+    let container = encrypt_data(&read_file(input)?, &key, algorithm)?;
+    write_file_atomic(output, &container)
+}
+
+/// Decrypt an encrypted file with a key file, returning the plaintext without writing it
+pub fn decrypt_file_to_memory<P: AsRef<Path>, K: AsRef<Path>>(
+    input: P,
+    key_file: K,
+) -> Result<Vec<u8>> {
+    let key = read_key_file(key_file)?;
+    decrypt_data(&read_file(input)?, &key)
+}
+
+/// Decrypt an encrypted file with a key file and write the plaintext to `output`.
+///
+/// Nothing is written unless authentication succeeds, and an existing `output`
+/// is replaced in one step rather than truncated first.
+pub fn decrypt_file<P: AsRef<Path>, Q: AsRef<Path>, K: AsRef<Path>>(
+    input: P,
+    output: Q,
+    key_file: K,
+) -> Result<()> {
+    let plaintext = decrypt_file_to_memory(input, key_file)?;
+    write_file_atomic(output, &plaintext)
+}
+
+/// Reassemble a container split with `--split-size` and decrypt it to `output`
+pub fn decrypt_parts<K: AsRef<Path>, Q: AsRef<Path>>(
+    parts: &[&Path],
+    key_file: K,
+    output: Q,
+) -> Result<()> {
+    let key = read_key_file(key_file)?;
+    let parts = parts.iter().map(read_file).collect::<Result<Vec<_>>>()?;
+    let container = crate::crypto::parts::join_parts(&parts)?;
+    write_file_atomic(output, &decrypt_data(&container, &key)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_cipher_for_each_algorithm() {
+        for name in ["aes-gcm", "aes-128-gcm", "aes-192-gcm", "aes-256-gcm"] {
+            let algorithm: EncryptionAlgorithm = name.parse().unwrap();
+            let key = vec![0u8; algorithm.key_length()];
+            let cipher = create_cipher(&algorithm, &key).unwrap();
+            assert_eq!(cipher.key_length(), algorithm.key_length());
+        }
+    }
+
+    #[test]
+    fn test_create_cipher_key_size_mismatch() {
+        let algorithm: EncryptionAlgorithm = "aes-256-gcm".parse().unwrap();
+        let err = create_cipher(&algorithm, &[0u8; 16]).err().unwrap();
+        assert!(err
+            .to_string()
+            .contains("aes-256-gcm needs a 32-byte key, got 16"));
+
+        let algorithm: EncryptionAlgorithm = "aes-128-gcm".parse().unwrap();
+        let err = create_cipher(&algorithm, &[0u8; 32]).err().unwrap();
+        assert!(err
+            .to_string()
+            .contains("aes-128-gcm needs a 16-byte key, got 32"));
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip_all_algorithms() {
+        let plaintext = b"engine round trip";
+        for algorithm in [
+            EncryptionAlgorithm::AesGcm,
+            EncryptionAlgorithm::Aes128Gcm,
+            EncryptionAlgorithm::Aes192Gcm,
+            EncryptionAlgorithm::ChaCha20Poly1305,
+        ] {
+            let key = crate::crypto::generate_key(&algorithm).unwrap();
+            let encrypted = encrypt_data(plaintext, &key, &algorithm).unwrap();
+            assert_eq!(decrypt_data(&encrypted, &key).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn test_convergent_encryption_is_deterministic() {
+        let plaintext = b"same module bytes";
+        for algorithm in [
+            EncryptionAlgorithm::AesGcm,
+            EncryptionAlgorithm::ChaCha20Poly1305,
+        ] {
+            let key = crate::crypto::generate_key(&algorithm).unwrap();
+
+            let first = encrypt_data_convergent(plaintext, &key, &algorithm).unwrap();
+            let second = encrypt_data_convergent(plaintext, &key, &algorithm).unwrap();
+            assert_eq!(first, second);
+            assert_eq!(decrypt_data(&first, &key).unwrap(), plaintext);
+
+            let first = encrypt_data(plaintext, &key, &algorithm).unwrap();
+            let second = encrypt_data(plaintext, &key, &algorithm).unwrap();
+            assert_ne!(first, second);
+        }
+    }
+
+    #[test]
+    fn test_convergent_iv_does_not_reuse_the_key_as_mac_key() {
+        let algorithm = EncryptionAlgorithm::AesGcm;
+        let key = crate::crypto::generate_key(&algorithm).unwrap();
+        let plaintext = b"same module bytes";
+
+        let container = encrypt_data_convergent(plaintext, &key, &algorithm).unwrap();
+        let mut mac = Hmac::<Sha256>::new_from_slice(&key).unwrap();
+        mac.update(plaintext);
+        assert_ne!(&container[..12], &mac.finalize().into_bytes()[..12]);
+    }
+
+    #[test]
+    fn test_decrypt_file_to_memory_matches_decrypt_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let encrypted_path = temp_dir.path().join("module.wasm.enc");
+        let key_path = temp_dir.path().join("module.key");
+        let output_path = temp_dir.path().join("module.wasm");
+
+        let algorithm = EncryptionAlgorithm::AesGcm;
+        let key = crate::crypto::generate_key(&algorithm).unwrap();
+        let plaintext = b"\0asm\x01\0\0\0";
+        crate::io::write_file(&output_path, plaintext).unwrap();
+        crate::io::write_key_file(&key_path, &key).unwrap();
+        encrypt_file(&output_path, &encrypted_path, &key_path, &algorithm).unwrap();
+
+        let in_memory = decrypt_file_to_memory(&encrypted_path, &key_path).unwrap();
+        decrypt_file(&encrypted_path, &output_path, &key_path).unwrap();
+        assert_eq!(in_memory, plaintext);
+        assert_eq!(read_file(&output_path).unwrap(), in_memory);
+    }
+
+    #[test]
+    fn test_decrypt_file_failure_writes_nothing() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let encrypted_path = temp_dir.path().join("module.wasm.enc");
+        let key_path = temp_dir.path().join("module.key");
+        let wrong_key_path = temp_dir.path().join("wrong.key");
+        let output_path = temp_dir.path().join("module.wasm");
+
+        let algorithm = EncryptionAlgorithm::AesGcm;
+        let plaintext = b"\0asm\x01\0\0\0";
+        crate::io::write_file(&encrypted_path, plaintext).unwrap();
+        crate::io::write_key_file(&key_path, &crate::crypto::generate_key(&algorithm).unwrap())
+            .unwrap();
+        crate::io::write_key_file(
+            &wrong_key_path,
+            &crate::crypto::generate_key(&algorithm).unwrap(),
+        )
+        .unwrap();
+
+        // Encrypting in place must read the input before replacing it
+        encrypt_file(&encrypted_path, &encrypted_path, &key_path, &algorithm).unwrap();
+
+        assert!(decrypt_file(&encrypted_path, &output_path, &wrong_key_path).is_err());
+        assert!(!output_path.exists());
+
+        crate::io::write_file(&output_path, b"existing").unwrap();
+        assert!(decrypt_file(&encrypted_path, &output_path, &wrong_key_path).is_err());
+        assert_eq!(read_file(&output_path).unwrap(), b"existing");
+
+        decrypt_file(&encrypted_path, &output_path, &key_path).unwrap();
+        assert_eq!(read_file(&output_path).unwrap(), plaintext);
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 4);
+    }
+
+    #[test]
+    fn test_decrypt_data_timed_metrics() {
+        let algorithm = EncryptionAlgorithm::ChaCha20Poly1305;
+        let key = crate::crypto::generate_key(&algorithm).unwrap();
+        let plaintext = vec![0x42u8; 256 * 1024];
+        let encrypted = encrypt_data(&plaintext, &key, &algorithm).unwrap();
+
+        let (decrypted, metrics) = decrypt_data_timed(&encrypted, &key).unwrap();
+        assert_eq!(decrypted, plaintext);
+        assert_eq!(metrics.algorithm, algorithm);
+        assert!(metrics.decrypt > Duration::ZERO);
+        assert!(metrics.total >= metrics.parse + metrics.kdf + metrics.decrypt);
+    }
+
+    #[test]
+    fn test_decrypt_data_unsupported_key_length() {
+        assert!(decrypt_data(&[0u8; 64], &[0u8; 20]).is_err());
+    }
+
+    #[test]
+    fn test_stream_round_trip_over_cursors() {
+        use std::io::Cursor;
+
+        let plaintext = b"\0asm\x01\0\0\0 streamed module".to_vec();
+        for algorithm in [
+            EncryptionAlgorithm::AesGcm,
+            EncryptionAlgorithm::ChaCha20Poly1305,
+        ] {
+            let key = vec![0x42u8; algorithm.key_length()];
+
+            let mut encrypted = Cursor::new(Vec::new());
+            encrypt_stream(
+                Cursor::new(plaintext.clone()),
+                &mut encrypted,
+                &key,
+                &algorithm,
+            )
+            .unwrap();
+
+            let mut decrypted = Cursor::new(Vec::new());
+            encrypted.set_position(0);
+            decrypt_stream(encrypted, &mut decrypted, &key).unwrap();
+            assert_eq!(decrypted.into_inner(), plaintext);
+        }
+    }
+
+    #[test]
+    fn test_truncated_tag_rejected() {
+        let algorithm = EncryptionAlgorithm::AesGcm;
+        let key = vec![0x42u8; algorithm.key_length()];
+        let encrypted = encrypt_data(b"", &key, &algorithm).unwrap();
+
+        // Empty plaintext: the ciphertext is only the 16-byte tag; keep 8 of it
+        let truncated = &encrypted[..encrypted.len() - 8];
+        let err = decrypt_data(truncated, &key).unwrap_err().to_string();
+        assert!(err.contains("truncated authentication tag"), "{}", err);
+
+        // A short container is rejected whatever the plaintext length was
+        let encrypted = encrypt_data(b"module", &key, &algorithm).unwrap();
+        let err = decrypt_data(&encrypted[..20], &key)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("need at least 28 bytes, got 20"), "{}", err);
+    }
+
+    #[test]
+    fn test_stream_progress_called_per_chunk() {
+        use std::io::Cursor;
+
+        let algorithm = EncryptionAlgorithm::AesGcm;
+        let key = vec![0x42u8; algorithm.key_length()];
+        let plaintext = vec![0xA5u8; 1000];
+
+        let mut calls = 0;
+        let mut encrypted = Vec::new();
+        encrypt_stream_with_progress(
+            Cursor::new(&plaintext),
+            &mut encrypted,
+            &key,
+            &algorithm,
+            100,
+            |_| calls += 1,
+        )
+        .unwrap();
+        assert_eq!(calls, 10);
+
+        // 12-byte IV + 1000 bytes + 16-byte tag = 1028 bytes in 100-byte chunks
+        let mut seen = Vec::new();
+        let mut decrypted = Vec::new();
+        decrypt_stream_with_progress(Cursor::new(&encrypted), &mut decrypted, &key, 100, |n| {
+            seen.push(n)
+        })
+        .unwrap();
+        assert_eq!(seen.len(), 11);
+        assert_eq!(seen.last(), Some(&1028));
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_benchmark_algorithms() {
+        let results = benchmark_algorithms(64 * 1024).unwrap();
+        for name in ["aes-256-gcm", "chacha20poly1305"] {
+            let throughput = results[name];
+            assert!(throughput.encrypt_mb_per_s > 0.0);
+            assert!(throughput.decrypt_mb_per_s > 0.0);
+        }
+        assert_eq!(results.len(), 4);
+        assert!(benchmark_algorithms(0).is_err());
+    }
+}
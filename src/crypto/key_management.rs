@@ -1,7 +1,9 @@
 use base64::Engine;
 use rand::RngCore;
+use std::path::{Path, PathBuf};
 
 use crate::cli::KeyFormat;
+use crate::config::EncryptionAlgorithm;
 use crate::error::{Result, RusWaCipherError};
 
 pub struct KeyManager;
@@ -33,26 +35,49 @@ impl KeyManager {
         Ok(iv)
     }
 
-    /// Decode a key from hexadecimal string
+    /// Strip a leading UTF-8 BOM and surrounding whitespace from key text
+    pub fn clean_key_text(text: &str) -> &str {
+        text.strip_prefix('\u{FEFF}').unwrap_or(text).trim()
+    }
+
+    /// Decode a key from hexadecimal string, tolerating a `0x` prefix and whitespace
     pub fn decode_hex_key(hex_key: &str) -> Result<Vec<u8>> {
-        hex::decode(hex_key.trim())
+        let text = Self::clean_key_text(hex_key);
+        let text = text
+            .strip_prefix("0x")
+            .or_else(|| text.strip_prefix("0X"))
+            .unwrap_or(text);
+        let digits: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+        hex::decode(digits)
             .map_err(|e| RusWaCipherError::KeyManagement(format!("Invalid hexadecimal key: {}", e)))
     }
 
     /// Decode a key from Base64 string
     pub fn decode_base64_key(base64_key: &str) -> Result<Vec<u8>> {
         base64::engine::general_purpose::STANDARD
-            .decode(base64_key.trim())
+            .decode(Self::clean_key_text(base64_key))
             .map_err(|e| RusWaCipherError::KeyManagement(format!("Invalid Base64 key: {}", e)))
     }
 
-    /// Encode a key to the specified format
+    /// Decode a key string in the specified format
+    pub fn decode_key(value: &str, format: &KeyFormat) -> Result<Vec<u8>> {
+        match format {
+            KeyFormat::Hex => Self::decode_hex_key(value),
+            KeyFormat::Base64 => Self::decode_base64_key(value),
+            KeyFormat::Raw => Ok(value.as_bytes().to_vec()),
+        }
+    }
+
+    /// Encode a key to the specified format for display.
+    ///
+    /// Raw keys are not printable, so they render as a placeholder; write them
+    /// with [`write_key_file_with_format`](crate::io::write_key_file_with_format).
     pub fn encode_key(key: &[u8], format: &KeyFormat) -> String {
         match format {
             KeyFormat::Hex => hex::encode(key),
             KeyFormat::Base64 => base64::engine::general_purpose::STANDARD.encode(key),
             KeyFormat::Raw => {
-                // For raw format, we'll return a warning message since it's not printable
                 format!(
                     "Raw binary key ({} bytes) - cannot display as text",
                     key.len()
@@ -66,44 +91,306 @@ impl KeyManager {
         key: &[u8],
         algorithm: &crate::config::EncryptionAlgorithm,
     ) -> Result<()> {
-        let expected_length = match algorithm {
-            crate::config::EncryptionAlgorithm::AesGcm => 32, // AES-256
-            crate::config::EncryptionAlgorithm::ChaCha20Poly1305 => 32, // ChaCha20
-        };
-
-        Self::validate_key_length(key, expected_length)?;
+        if key.len() != algorithm.key_length() {
+            return Err(RusWaCipherError::KeyManagement(format!(
+                "{} needs a {}-byte key, got {}",
+                algorithm.canonical_name(),
+                algorithm.key_length(),
+                key.len()
+            )));
+        }
         Ok(())
     }
 }
 
 /// Generate a key for the specified algorithm
 pub fn generate_key(algorithm: &crate::config::EncryptionAlgorithm) -> Result<Vec<u8>> {
-    let key_length = match algorithm {
-        crate::config::EncryptionAlgorithm::AesGcm => 32, // AES-256
-        crate::config::EncryptionAlgorithm::ChaCha20Poly1305 => 32, // ChaCha20
-    };
+    KeyManager::generate_key(algorithm.key_length())
+}
 
-    KeyManager::generate_key(key_length)
+/// Where a generated key goes when no `--generate-key` path was given: `<output>.key`
+pub fn default_key_path(output: &Path) -> PathBuf {
+    let mut path = output.as_os_str().to_owned();
+    path.push(".key");
+    PathBuf::from(path)
 }
 
-/// Resolve key from various sources (file, hex, base64) based on configuration
-pub fn resolve_key(config: &crate::config::EncryptionConfig) -> Result<Option<Vec<u8>>> {
-    // Priority: key_hex > key_base64 > key_file
+/// A source of key material, e.g. a file, an environment variable or a KMS
+pub trait KeyProvider {
+    /// Human-readable description used in log messages
+    fn name(&self) -> String;
+
+    /// Whether this provider can currently supply a key.
+    /// Unavailable providers are skipped during resolution.
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    /// Fetch the key for the given algorithm
+    fn resolve(&self, algorithm: &EncryptionAlgorithm) -> Result<Vec<u8>>;
+
+    /// Fetch the key before the algorithm is known, as when decrypting.
+    /// Providers that check the key length against the algorithm override this.
+    fn resolve_any(&self) -> Result<Vec<u8>> {
+        self.resolve(&EncryptionAlgorithm::AesGcm)
+    }
+}
+
+/// Reads a key from a key file (hex, base64 or raw)
+pub struct FileKeyProvider {
+    pub path: PathBuf,
+}
+
+impl FileKeyProvider {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        FileKeyProvider { path: path.into() }
+    }
+}
+
+impl KeyProvider for FileKeyProvider {
+    fn name(&self) -> String {
+        format!("file {:?}", self.path)
+    }
+
+    fn resolve(&self, algorithm: &EncryptionAlgorithm) -> Result<Vec<u8>> {
+        let key = crate::io::read_key_file(&self.path)?;
+        let expected = algorithm.key_length();
+        if key.len() != expected {
+            return Err(RusWaCipherError::KeyManagement(format!(
+                "Key file {:?} decodes to {} bytes, but {} needs {}; \
+                 key files hold the key as hex ({} characters), base64 ({} characters) or raw bytes",
+                self.path,
+                key.len(),
+                algorithm.canonical_name(),
+                expected,
+                expected * 2,
+                expected.div_ceil(3) * 4
+            )));
+        }
+        Ok(key)
+    }
+
+    fn resolve_any(&self) -> Result<Vec<u8>> {
+        crate::io::read_key_file(&self.path)
+    }
+}
+
+/// Reads an encoded key from an environment variable
+pub struct EnvKeyProvider {
+    pub variable: String,
+    pub format: KeyFormat,
+}
+
+impl EnvKeyProvider {
+    pub fn new(variable: &str, format: KeyFormat) -> Self {
+        EnvKeyProvider {
+            variable: variable.to_string(),
+            format,
+        }
+    }
+
+    /// Read and decode the key from the variable
+    pub fn load(&self) -> Result<Vec<u8>> {
+        let value = std::env::var(&self.variable).map_err(|_| {
+            RusWaCipherError::KeyManagement(format!(
+                "Environment variable {} is not set",
+                self.variable
+            ))
+        })?;
+        KeyManager::decode_key(&value, &self.format)
+    }
+}
+
+impl KeyProvider for EnvKeyProvider {
+    fn name(&self) -> String {
+        format!("environment variable {}", self.variable)
+    }
+
+    fn resolve(&self, _algorithm: &EncryptionAlgorithm) -> Result<Vec<u8>> {
+        self.load()
+    }
+}
+
+/// Decodes a key passed directly as a string
+pub struct InlineKeyProvider {
+    pub value: String,
+    pub format: KeyFormat,
+}
+
+impl InlineKeyProvider {
+    pub fn new(value: &str, format: KeyFormat) -> Self {
+        InlineKeyProvider {
+            value: value.to_string(),
+            format,
+        }
+    }
+}
+
+impl KeyProvider for InlineKeyProvider {
+    fn name(&self) -> String {
+        "inline key".to_string()
+    }
+
+    fn resolve(&self, _algorithm: &EncryptionAlgorithm) -> Result<Vec<u8>> {
+        KeyManager::decode_key(&self.value, &self.format)
+    }
+}
+
+/// Stores and fetches keys in the OS secret store under a service and account
+#[cfg(feature = "keyring")]
+pub struct KeyringKeyProvider {
+    pub service: String,
+    pub account: String,
+    entry: keyring::Entry,
+}
+
+#[cfg(feature = "keyring")]
+impl KeyringKeyProvider {
+    pub fn new(service: &str, account: &str) -> Result<Self> {
+        let entry = keyring::Entry::new(service, account).map_err(keyring_error)?;
+        Ok(KeyringKeyProvider {
+            service: service.to_string(),
+            account: account.to_string(),
+            entry,
+        })
+    }
+
+    /// Fetch the stored key
+    pub fn load(&self) -> Result<Vec<u8>> {
+        self.entry.get_secret().map_err(keyring_error)
+    }
+
+    /// Store a key, replacing any existing entry
+    pub fn store(&self, key: &[u8]) -> Result<()> {
+        self.entry.set_secret(key).map_err(keyring_error)
+    }
+}
+
+#[cfg(feature = "keyring")]
+impl KeyProvider for KeyringKeyProvider {
+    fn name(&self) -> String {
+        format!("keyring entry {}/{}", self.service, self.account)
+    }
+
+    /// A missing entry lets resolution fall through to the next key source;
+    /// other keyring errors still surface from [`resolve`](KeyProvider::resolve)
+    fn is_available(&self) -> bool {
+        match self.entry.get_secret() {
+            Err(keyring::Error::NoEntry) => {
+                log::warn!("{} does not exist", self.name());
+                false
+            }
+            _ => true,
+        }
+    }
+
+    fn resolve(&self, _algorithm: &EncryptionAlgorithm) -> Result<Vec<u8>> {
+        self.load()
+    }
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_error(err: keyring::Error) -> RusWaCipherError {
+    RusWaCipherError::KeyManagement(format!("Keyring error: {}", err))
+}
+
+/// Create a provider for a keyring entry
+#[cfg(feature = "keyring")]
+pub fn keyring_provider(service: &str, account: &str) -> Result<Box<dyn KeyProvider>> {
+    Ok(Box::new(KeyringKeyProvider::new(service, account)?))
+}
+
+/// Create a provider for a keyring entry
+#[cfg(not(feature = "keyring"))]
+pub fn keyring_provider(_service: &str, _account: &str) -> Result<Box<dyn KeyProvider>> {
+    Err(keyring_unsupported())
+}
+
+/// Fetch a key from a keyring entry
+#[cfg(feature = "keyring")]
+pub fn load_keyring_key(service: &str, account: &str) -> Result<Vec<u8>> {
+    KeyringKeyProvider::new(service, account)?.load()
+}
+
+/// Fetch a key from a keyring entry
+#[cfg(not(feature = "keyring"))]
+pub fn load_keyring_key(_service: &str, _account: &str) -> Result<Vec<u8>> {
+    Err(keyring_unsupported())
+}
+
+/// Store a key in a keyring entry, replacing any existing value
+#[cfg(feature = "keyring")]
+pub fn store_keyring_key(service: &str, account: &str, key: &[u8]) -> Result<()> {
+    KeyringKeyProvider::new(service, account)?.store(key)
+}
+
+/// Store a key in a keyring entry, replacing any existing value
+#[cfg(not(feature = "keyring"))]
+pub fn store_keyring_key(_service: &str, _account: &str, _key: &[u8]) -> Result<()> {
+    Err(keyring_unsupported())
+}
+
+#[cfg(not(feature = "keyring"))]
+fn keyring_unsupported() -> RusWaCipherError {
+    RusWaCipherError::Config(
+        "Keyring support is not compiled in; rebuild with --features keyring".to_string(),
+    )
+}
+
+/// Build the prioritized provider list for an encryption configuration.
+///
+/// Priority: key_hex > key_base64 > key_env > key_file > keyring. The keyring is
+/// skipped when generating a key, since the new key is stored there instead.
+pub fn providers_from_config(
+    config: &crate::config::EncryptionConfig,
+) -> Result<Vec<Box<dyn KeyProvider>>> {
+    let mut providers: Vec<Box<dyn KeyProvider>> = Vec::new();
+
     if let Some(hex_key) = &config.key_hex {
-        let key = KeyManager::decode_hex_key(hex_key)?;
-        KeyManager::validate_key_for_algorithm(&key, &config.algorithm)?;
-        return Ok(Some(key));
+        providers.push(Box::new(InlineKeyProvider::new(hex_key, KeyFormat::Hex)));
     }
 
     if let Some(base64_key) = &config.key_base64 {
-        let key = KeyManager::decode_base64_key(base64_key)?;
-        KeyManager::validate_key_for_algorithm(&key, &config.algorithm)?;
-        return Ok(Some(key));
+        providers.push(Box::new(InlineKeyProvider::new(
+            base64_key,
+            KeyFormat::Base64,
+        )));
+    }
+
+    if let Some(variable) = &config.key_env {
+        providers.push(Box::new(EnvKeyProvider::new(
+            variable,
+            config.key_env_format.clone(),
+        )));
     }
 
     if let Some(key_file) = &config.key_file {
-        let key = crate::io::read_key_file(key_file)?;
-        KeyManager::validate_key_for_algorithm(&key, &config.algorithm)?;
+        providers.push(Box::new(FileKeyProvider::new(key_file)));
+    }
+
+    if let (Some(service), Some(account)) = (&config.keyring_service, &config.keyring_account) {
+        if !config.generate_key {
+            providers.push(keyring_provider(service, account)?);
+        }
+    }
+
+    Ok(providers)
+}
+
+/// Resolve a key from the first available provider in the list
+pub fn resolve_key_with_providers(
+    providers: &[Box<dyn KeyProvider>],
+    algorithm: &EncryptionAlgorithm,
+) -> Result<Option<Vec<u8>>> {
+    for provider in providers {
+        if !provider.is_available() {
+            log::debug!("Key provider unavailable: {}", provider.name());
+            continue;
+        }
+
+        log::debug!("Resolving key from {}", provider.name());
+        let key = provider.resolve(algorithm)?;
+        KeyManager::validate_key_for_algorithm(&key, algorithm)?;
         return Ok(Some(key));
     }
 
@@ -111,6 +398,56 @@ pub fn resolve_key(config: &crate::config::EncryptionConfig) -> Result<Option<Ve
     Ok(None)
 }
 
+/// Resolve key from various sources (file, hex, base64) based on configuration
+pub fn resolve_key(config: &crate::config::EncryptionConfig) -> Result<Option<Vec<u8>>> {
+    resolve_key_with_providers(&providers_from_config(config)?, &config.algorithm)
+}
+
+/// Build the prioritized provider list for a decryption configuration.
+///
+/// Priority: key_file > key_env > keyring. A key embedded with `--embed-key` is
+/// not a provider; callers fall back to it when no provider supplies a key.
+pub fn decryption_providers(
+    config: &crate::config::DecryptionConfig,
+) -> Result<Vec<Box<dyn KeyProvider>>> {
+    let mut providers: Vec<Box<dyn KeyProvider>> = Vec::new();
+
+    if let Some(key_file) = &config.key_file {
+        providers.push(Box::new(FileKeyProvider::new(key_file)));
+    }
+
+    if let Some(variable) = &config.key_env {
+        providers.push(Box::new(EnvKeyProvider::new(
+            variable,
+            config.key_env_format.clone(),
+        )));
+    }
+
+    if let (Some(service), Some(account)) = (&config.keyring_service, &config.keyring_account) {
+        providers.push(keyring_provider(service, account)?);
+    }
+
+    Ok(providers)
+}
+
+/// Resolve the decryption key from the first available provider in the list.
+///
+/// The algorithm is not known yet, so only the key's encoding is checked here;
+/// decryption picks the algorithm from the key length.
+pub fn resolve_decryption_key(config: &crate::config::DecryptionConfig) -> Result<Option<Vec<u8>>> {
+    for provider in decryption_providers(config)? {
+        if !provider.is_available() {
+            log::debug!("Key provider unavailable: {}", provider.name());
+            continue;
+        }
+
+        log::info!("Reading key from {}", provider.name());
+        return provider.resolve_any().map(Some);
+    }
+
+    Ok(None)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,6 +487,27 @@ mod tests {
         let chacha_key =
             generate_key(&crate::config::EncryptionAlgorithm::ChaCha20Poly1305).unwrap();
         assert_eq!(chacha_key.len(), 32);
+
+        let aes128_key = generate_key(&crate::config::EncryptionAlgorithm::Aes128Gcm).unwrap();
+        assert_eq!(aes128_key.len(), 16);
+    }
+
+    #[test]
+    fn test_validate_key_for_algorithm_mismatch_message() {
+        let err = KeyManager::validate_key_for_algorithm(
+            &[0u8; 16],
+            &crate::config::EncryptionAlgorithm::AesGcm,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("aes-256-gcm needs a 32-byte key, got 16"));
+
+        assert!(KeyManager::validate_key_for_algorithm(
+            &[0u8; 24],
+            &crate::config::EncryptionAlgorithm::Aes192Gcm
+        )
+        .is_ok());
     }
 
     #[test]
@@ -234,17 +592,10 @@ mod tests {
     #[test]
     fn test_resolve_key_hex() {
         let hex_key = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
-        let config = crate::config::EncryptionConfig {
-            algorithm: crate::config::EncryptionAlgorithm::AesGcm,
-            input_file: std::path::PathBuf::new(),
-            output_file: std::path::PathBuf::new(),
-            key_file: None,
-            key_hex: Some(hex_key.to_string()),
-            key_base64: None,
-            generate_key: false,
-            key_output_file: None,
-            key_format: KeyFormat::Hex,
-        };
+        let config = crate::config::EncryptionConfig::builder("", "")
+            .key_hex(hex_key)
+            .build()
+            .unwrap();
 
         let resolved_key = resolve_key(&config).unwrap().unwrap();
         assert_eq!(resolved_key.len(), 32);
@@ -255,37 +606,244 @@ mod tests {
     fn test_resolve_key_base64() {
         let key_bytes = vec![0u8; 32];
         let base64_key = base64::engine::general_purpose::STANDARD.encode(&key_bytes);
-        let config = crate::config::EncryptionConfig {
-            algorithm: crate::config::EncryptionAlgorithm::AesGcm,
+        let config = crate::config::EncryptionConfig::builder("", "")
+            .key_base64(&base64_key)
+            .key_format(KeyFormat::Base64)
+            .build()
+            .unwrap();
+
+        let resolved_key = resolve_key(&config).unwrap().unwrap();
+        assert_eq!(resolved_key, key_bytes);
+    }
+
+    #[test]
+    fn test_resolve_key_env() {
+        let key_bytes = vec![9u8; 32];
+        std::env::set_var(
+            "RUSWACIPHER_TEST_RESOLVE_ENV_KEY",
+            base64::engine::general_purpose::STANDARD.encode(&key_bytes),
+        );
+        let mut config = crate::config::EncryptionConfig::builder("", "")
+            .key_env("RUSWACIPHER_TEST_RESOLVE_ENV_KEY")
+            .key_env_format(KeyFormat::Base64)
+            .build()
+            .unwrap();
+
+        let resolved_key = resolve_key(&config).unwrap().unwrap();
+        assert_eq!(resolved_key, key_bytes);
+
+        // Base64 content read as hex is an encoding error, not a silent fallback
+        config.key_env_format = KeyFormat::Hex;
+        assert!(resolve_key(&config).is_err());
+
+        config.key_env = Some("RUSWACIPHER_TEST_RESOLVE_ENV_UNSET".to_string());
+        let err = resolve_key(&config).unwrap_err();
+        assert!(err.to_string().contains("is not set"));
+    }
+
+    #[test]
+    fn test_resolve_decryption_key() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let key_file = dir.path().join("aes128.key");
+        std::fs::write(&key_file, hex::encode([3u8; 16])).unwrap();
+        std::env::set_var("RUSWACIPHER_TEST_DECRYPT_ENV_KEY", hex::encode([4u8; 32]));
+
+        let mut config = crate::config::DecryptionConfig {
             input_file: std::path::PathBuf::new(),
             output_file: std::path::PathBuf::new(),
             key_file: None,
-            key_hex: None,
-            key_base64: Some(base64_key),
-            generate_key: false,
-            key_output_file: None,
-            key_format: KeyFormat::Base64,
+            key_env: None,
+            key_env_format: KeyFormat::Hex,
+            keyring_service: None,
+            keyring_account: None,
+            clean: false,
+            buffer_size: crate::io::DEFAULT_BUFFER_SIZE,
+            verify_key: None,
+            insecure: false,
+            parts: false,
         };
+        assert!(resolve_decryption_key(&config).unwrap().is_none());
 
-        let resolved_key = resolve_key(&config).unwrap().unwrap();
-        assert_eq!(resolved_key, key_bytes);
+        config.key_env = Some("RUSWACIPHER_TEST_DECRYPT_ENV_KEY".to_string());
+        assert_eq!(
+            resolve_decryption_key(&config).unwrap().unwrap(),
+            vec![4u8; 32]
+        );
+
+        // The key file wins, and a 16-byte key is not checked against a default algorithm
+        config.key_file = Some(key_file);
+        assert_eq!(
+            resolve_decryption_key(&config).unwrap().unwrap(),
+            vec![3u8; 16]
+        );
     }
 
     #[test]
     fn test_resolve_key_none() {
-        let config = crate::config::EncryptionConfig {
-            algorithm: crate::config::EncryptionAlgorithm::AesGcm,
+        let config = crate::config::EncryptionConfig::builder("", "")
+            .build()
+            .unwrap();
+
+        let resolved_key = resolve_key(&config).unwrap();
+        assert!(resolved_key.is_none());
+    }
+
+    struct UnavailableProvider;
+
+    impl KeyProvider for UnavailableProvider {
+        fn name(&self) -> String {
+            "unavailable".to_string()
+        }
+
+        fn is_available(&self) -> bool {
+            false
+        }
+
+        fn resolve(&self, _algorithm: &EncryptionAlgorithm) -> Result<Vec<u8>> {
+            panic!("unavailable provider must not be resolved");
+        }
+    }
+
+    #[test]
+    fn test_env_key_provider() {
+        let key_bytes = vec![7u8; 32];
+        std::env::set_var(
+            "RUSWACIPHER_TEST_ENV_PROVIDER_KEY",
+            base64::engine::general_purpose::STANDARD.encode(&key_bytes),
+        );
+
+        let provider = EnvKeyProvider::new("RUSWACIPHER_TEST_ENV_PROVIDER_KEY", KeyFormat::Base64);
+        assert_eq!(
+            provider.resolve(&EncryptionAlgorithm::AesGcm).unwrap(),
+            key_bytes
+        );
+
+        let missing = EnvKeyProvider::new("RUSWACIPHER_TEST_ENV_PROVIDER_UNSET", KeyFormat::Hex);
+        assert!(missing.resolve(&EncryptionAlgorithm::AesGcm).is_err());
+    }
+
+    #[test]
+    fn test_inline_key_provider() {
+        let hex_key = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+        let provider = InlineKeyProvider::new(hex_key, KeyFormat::Hex);
+        let key = provider.resolve(&EncryptionAlgorithm::AesGcm).unwrap();
+        assert_eq!(hex::encode(key), hex_key);
+
+        let invalid = InlineKeyProvider::new("not hex", KeyFormat::Hex);
+        assert!(invalid.resolve(&EncryptionAlgorithm::AesGcm).is_err());
+    }
+
+    #[cfg(feature = "keyring")]
+    #[test]
+    fn test_keyring_store_then_fetch() {
+        keyring::set_default_credential_builder(keyring::mock::default_credential_builder());
+
+        // Mock entries do not persist across instances, so use a single provider
+        let provider = KeyringKeyProvider::new("ruswacipher-test", "module").unwrap();
+        assert!(provider.resolve(&EncryptionAlgorithm::AesGcm).is_err());
+
+        let key = KeyManager::generate_key(32).unwrap();
+        provider.store(&key).unwrap();
+        assert_eq!(provider.resolve(&EncryptionAlgorithm::AesGcm).unwrap(), key);
+    }
+
+    #[cfg(feature = "keyring")]
+    #[test]
+    fn test_missing_keyring_entry_falls_back() {
+        keyring::set_default_credential_builder(keyring::mock::default_credential_builder());
+
+        let provider = KeyringKeyProvider::new("ruswacipher-test", "missing").unwrap();
+        assert!(!provider.is_available());
+
+        let fallback = vec![6u8; 32];
+        let providers: Vec<Box<dyn KeyProvider>> = vec![
+            Box::new(provider),
+            Box::new(InlineKeyProvider::new(
+                &hex::encode(&fallback),
+                KeyFormat::Hex,
+            )),
+        ];
+        let key = resolve_key_with_providers(&providers, &EncryptionAlgorithm::AesGcm)
+            .unwrap()
+            .unwrap();
+        assert_eq!(key, fallback);
+
+        // With no other source, decryption is left to try an embedded key
+        let config = crate::config::DecryptionConfig {
             input_file: std::path::PathBuf::new(),
             output_file: std::path::PathBuf::new(),
             key_file: None,
-            key_hex: None,
-            key_base64: None,
-            generate_key: false,
-            key_output_file: None,
-            key_format: KeyFormat::Hex,
+            key_env: None,
+            key_env_format: KeyFormat::Hex,
+            keyring_service: Some("ruswacipher-test".to_string()),
+            keyring_account: Some("missing".to_string()),
+            clean: false,
+            buffer_size: crate::io::DEFAULT_BUFFER_SIZE,
+            verify_key: None,
+            insecure: false,
+            parts: false,
         };
+        assert!(resolve_decryption_key(&config).unwrap().is_none());
+    }
 
-        let resolved_key = resolve_key(&config).unwrap();
-        assert!(resolved_key.is_none());
+    #[test]
+    fn test_provider_priority_ordering() {
+        let first = vec![1u8; 32];
+        let second = vec![2u8; 32];
+        let providers: Vec<Box<dyn KeyProvider>> = vec![
+            Box::new(UnavailableProvider),
+            Box::new(InlineKeyProvider::new(&hex::encode(&first), KeyFormat::Hex)),
+            Box::new(InlineKeyProvider::new(
+                &hex::encode(&second),
+                KeyFormat::Hex,
+            )),
+        ];
+
+        let key = resolve_key_with_providers(&providers, &EncryptionAlgorithm::AesGcm)
+            .unwrap()
+            .unwrap();
+        assert_eq!(key, first);
+
+        let providers: Vec<Box<dyn KeyProvider>> = vec![Box::new(UnavailableProvider)];
+        assert!(
+            resolve_key_with_providers(&providers, &EncryptionAlgorithm::AesGcm)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_file_key_wrong_length_names_file() {
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        crate::io::write_key_file(temp_file.path(), &[0u8; 16]).unwrap();
+
+        let provider = FileKeyProvider::new(temp_file.path());
+        let err = provider
+            .resolve(&EncryptionAlgorithm::AesGcm)
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains(&format!("{:?}", temp_file.path())), "{}", err);
+        assert!(err.contains("decodes to 16 bytes"));
+        assert!(err.contains("aes-256-gcm needs 32"));
+        assert!(err.contains("hex (64 characters), base64 (44 characters)"));
+    }
+
+    #[test]
+    fn test_decode_key_tolerates_bom_and_prefix() {
+        let key = vec![0xABu8, 0xCD, 0xEF, 0x01];
+
+        let with_bom = format!(
+            "\u{FEFF}{}\r\n",
+            base64::engine::general_purpose::STANDARD.encode(&key)
+        );
+        assert_eq!(KeyManager::decode_base64_key(&with_bom).unwrap(), key);
+
+        assert_eq!(KeyManager::decode_hex_key("0xabcdef01").unwrap(), key);
+        assert_eq!(
+            KeyManager::decode_hex_key("\u{FEFF}0XAB CD\nEF 01\n").unwrap(),
+            key
+        );
+        assert!(KeyManager::decode_hex_key("0x").unwrap().is_empty());
     }
 }
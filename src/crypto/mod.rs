@@ -1,9 +1,29 @@
 pub mod aes_gcm;
+pub mod audit;
+pub mod bundle;
 pub mod chacha20poly1305;
+pub mod embedded_key;
+pub mod engine;
+pub mod integrity;
 pub mod key_management;
+pub mod parts;
+pub mod signing;
 pub mod traits;
 
-pub use aes_gcm::AesGcmCipher;
+pub use aes_gcm::{AesGcmCipher, AesGcmStreamDecryptor, AesGcmStreamEncryptor};
+pub use audit::{clear_audit_sink, jsonl_file_sink, set_audit_sink, AuditEvent, AuditOperation};
+pub use bundle::{decrypt_bundle, encrypt_bundle, BundleEntry};
 pub use chacha20poly1305::ChaCha20Poly1305Cipher;
-pub use key_management::{generate_key, KeyManager};
-pub use traits::{Cipher, EncryptionResult};
+pub use engine::{
+    benchmark_algorithms, create_cipher, decrypt_data, decrypt_data_timed, decrypt_file,
+    decrypt_file_to_memory, decrypt_parts, decrypt_stream, decrypt_stream_with_progress,
+    encrypt_data, encrypt_data_convergent, encrypt_file, encrypt_stream,
+    encrypt_stream_with_progress, DecryptMetrics, Throughput,
+};
+pub use integrity::{sri_hash, SriAlgorithm};
+pub use key_management::{
+    generate_key, EnvKeyProvider, FileKeyProvider, InlineKeyProvider, KeyManager, KeyProvider,
+};
+pub use traits::{
+    Cipher, EncryptionResult, StreamingCipher, STREAM_NONCE_LENGTH, STREAM_SEGMENT_SIZE,
+};
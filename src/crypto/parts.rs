@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::{Result, RusWaCipherError};
+use crate::io::read_file;
+
+/// Marker at the start of every part of a split container
+pub const PART_MAGIC: &[u8; 4] = b"RWCP";
+
+const HEADER_LENGTH: usize = PART_MAGIC.len() + 4 + 4;
+
+/// Path of part `index` of a split container: `<output>.000`, `<output>.001`, ...
+pub fn part_path(output: &Path, index: usize) -> PathBuf {
+    let mut path = output.as_os_str().to_owned();
+    path.push(format!(".{:03}", index));
+    PathBuf::from(path)
+}
+
+/// Split a container into parts of at most `part_size` data bytes.
+///
+/// Each part is `[magic][index: u32 LE][part count: u32 LE][data]`, so a missing,
+/// duplicated or reordered part is detected before decryption.
+pub fn split_parts(container: &[u8], part_size: usize) -> Result<Vec<Vec<u8>>> {
+    if part_size == 0 {
+        return Err(RusWaCipherError::InvalidInput(
+            "Split size must be at least 1 byte".to_string(),
+        ));
+    }
+
+    let chunks: Vec<&[u8]> = container.chunks(part_size).collect();
+    let count = chunks.len() as u32;
+    Ok(chunks
+        .into_iter()
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut part = Vec::with_capacity(HEADER_LENGTH + chunk.len());
+            part.extend_from_slice(PART_MAGIC);
+            part.extend_from_slice(&(index as u32).to_le_bytes());
+            part.extend_from_slice(&count.to_le_bytes());
+            part.extend_from_slice(chunk);
+            part
+        })
+        .collect())
+}
+
+/// Reassemble parts produced by [`split_parts`], given in order
+pub fn join_parts<B: AsRef<[u8]>>(parts: &[B]) -> Result<Vec<u8>> {
+    let mut container = Vec::new();
+    for (expected, part) in parts.iter().enumerate() {
+        let part = part.as_ref();
+        if part.len() < HEADER_LENGTH || !part.starts_with(PART_MAGIC) {
+            return Err(RusWaCipherError::InvalidInput(format!(
+                "Part {} is not a split container part",
+                expected
+            )));
+        }
+
+        let index = u32::from_le_bytes(part[4..8].try_into().unwrap()) as usize;
+        let count = u32::from_le_bytes(part[8..12].try_into().unwrap()) as usize;
+        if index != expected {
+            return Err(RusWaCipherError::InvalidInput(format!(
+                "Part {} has index {}; parts must be given in order",
+                expected, index
+            )));
+        }
+        if count != parts.len() {
+            return Err(RusWaCipherError::InvalidInput(format!(
+                "Container was split into {} parts, but {} were given",
+                count,
+                parts.len()
+            )));
+        }
+        container.extend_from_slice(&part[HEADER_LENGTH..]);
+    }
+
+    if container.is_empty() {
+        return Err(RusWaCipherError::InvalidInput(
+            "No parts to reassemble".to_string(),
+        ));
+    }
+    Ok(container)
+}
+
+/// Read `<output>.000`, `<output>.001`, ... until the next part is missing and reassemble them
+pub fn read_parts(output: &Path) -> Result<Vec<u8>> {
+    let first = part_path(output, 0);
+    if !first.exists() {
+        return Err(RusWaCipherError::InvalidInput(format!(
+            "No split parts found: {:?} does not exist",
+            first
+        )));
+    }
+
+    let parts = (0..)
+        .map(|index| part_path(output, index))
+        .take_while(|path| path.exists())
+        .map(read_file)
+        .collect::<Result<Vec<_>>>()?;
+    join_parts(&parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_and_join_round_trip() {
+        let container: Vec<u8> = (0..=255).collect();
+        let parts = split_parts(&container, 100).unwrap();
+        assert_eq!(parts.len(), 3);
+        assert!(parts.iter().all(|p| p.starts_with(PART_MAGIC)));
+        assert_eq!(join_parts(&parts).unwrap(), container);
+    }
+
+    #[test]
+    fn test_join_rejects_reordered_or_missing_parts() {
+        let container = vec![7u8; 30];
+        let mut parts = split_parts(&container, 10).unwrap();
+
+        parts.swap(0, 1);
+        let err = join_parts(&parts).unwrap_err().to_string();
+        assert!(err.contains("Part 0 has index 1"));
+
+        parts.swap(0, 1);
+        let err = join_parts(&parts[..2]).unwrap_err().to_string();
+        assert!(err.contains("split into 3 parts, but 2 were given"));
+    }
+
+    #[test]
+    fn test_read_parts_from_disk() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let output = dir.path().join("app.wasm");
+        assert!(read_parts(&output)
+            .unwrap_err()
+            .to_string()
+            .contains("No split parts found"));
+
+        let container: Vec<u8> = (0..50).collect();
+        for (index, part) in split_parts(&container, 20).unwrap().iter().enumerate() {
+            std::fs::write(part_path(&output, index), part).unwrap();
+        }
+        assert_eq!(read_parts(&output).unwrap(), container);
+
+        std::fs::remove_file(part_path(&output, 2)).unwrap();
+        assert!(read_parts(&output)
+            .unwrap_err()
+            .to_string()
+            .contains("split into 3 parts, but 2 were given"));
+    }
+
+    #[test]
+    fn test_part_path() {
+        assert_eq!(
+            part_path(Path::new("out/app.wasm"), 2),
+            PathBuf::from("out/app.wasm.002")
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+use crate::error::{Result, RusWaCipherError};
+
+/// Path of the detached signature for an encrypted file: `<output>.sig`
+pub fn signature_path(output: &Path) -> PathBuf {
+    let mut path = output.as_os_str().to_owned();
+    path.push(".sig");
+    PathBuf::from(path)
+}
+
+/// Sign the encrypted container with a 32-byte Ed25519 private key (seed),
+/// returning the 64-byte detached signature
+pub fn sign_container(data: &[u8], private_key: &[u8]) -> Result<Vec<u8>> {
+    let seed: [u8; 32] = private_key.try_into().map_err(|_| {
+        RusWaCipherError::KeyManagement(format!(
+            "Ed25519 private key must be 32 bytes, got {}",
+            private_key.len()
+        ))
+    })?;
+    let signing_key = SigningKey::from_bytes(&seed);
+    Ok(signing_key.sign(data).to_bytes().to_vec())
+}
+
+/// Verify a detached signature over the encrypted container with a 32-byte Ed25519 public key
+pub fn verify_container(data: &[u8], signature: &[u8], public_key: &[u8]) -> Result<()> {
+    let public_key: [u8; 32] = public_key.try_into().map_err(|_| {
+        RusWaCipherError::KeyManagement(format!(
+            "Ed25519 public key must be 32 bytes, got {}",
+            public_key.len()
+        ))
+    })?;
+    let verifying_key = VerifyingKey::from_bytes(&public_key).map_err(|e| {
+        RusWaCipherError::KeyManagement(format!("Invalid Ed25519 public key: {}", e))
+    })?;
+    let signature = Signature::from_slice(signature).map_err(|_| {
+        RusWaCipherError::Decryption(format!(
+            "Invalid signature: expected 64 bytes, got {}",
+            signature.len()
+        ))
+    })?;
+
+    verifying_key.verify(data, &signature).map_err(|_| {
+        RusWaCipherError::Authentication(
+            "Signature verification failed: the file was modified or signed by another key"
+                .to_string(),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let seed = [7u8; 32];
+        let public_key = SigningKey::from_bytes(&seed).verifying_key().to_bytes();
+        let container = b"nonce and ciphertext".to_vec();
+
+        let signature = sign_container(&container, &seed).unwrap();
+        assert_eq!(signature.len(), 64);
+        assert!(verify_container(&container, &signature, &public_key).is_ok());
+
+        let mut tampered = container.clone();
+        tampered[0] ^= 1;
+        let err = verify_container(&tampered, &signature, &public_key)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Signature verification failed"));
+    }
+
+    #[test]
+    fn test_signature_path() {
+        assert_eq!(
+            signature_path(Path::new("out/module.wasm")),
+            PathBuf::from("out/module.wasm.sig")
+        );
+    }
+}
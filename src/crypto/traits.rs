@@ -37,6 +37,14 @@ pub trait Cipher {
     /// Encrypt data and return IV + ciphertext
     fn encrypt(&self, plaintext: &[u8]) -> Result<EncryptionResult>;
 
+    /// Encrypt data with a caller-supplied IV; the IV must never be reused
+    /// with the same key for different plaintexts
+    fn encrypt_with_iv(&self, _iv: &[u8], _plaintext: &[u8]) -> Result<EncryptionResult> {
+        Err(crate::error::RusWaCipherError::Encryption(
+            "This cipher does not support caller-supplied IVs".to_string(),
+        ))
+    }
+
     /// Decrypt data using provided IV and ciphertext
     fn decrypt(&self, iv: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>>;
 
@@ -45,6 +53,49 @@ pub trait Cipher {
 
     /// Get the key length for this cipher
     fn key_length(&self) -> usize;
+
+    /// Get the authentication tag length appended to the ciphertext
+    fn tag_length(&self) -> usize {
+        16
+    }
+
+    /// Create an incremental encryptor, for ciphers that support streaming
+    fn streaming_encryptor(&self) -> Result<Box<dyn StreamingCipher>> {
+        Err(crate::error::RusWaCipherError::Encryption(
+            "This cipher does not support streaming encryption".to_string(),
+        ))
+    }
+
+    /// Create an incremental decryptor for the output of [`Cipher::streaming_encryptor`]
+    fn streaming_decryptor(&self) -> Result<Box<dyn StreamingCipher>> {
+        Err(crate::error::RusWaCipherError::Decryption(
+            "This cipher does not support streaming decryption".to_string(),
+        ))
+    }
+}
+
+/// Plaintext bytes per segment in streaming mode
+pub const STREAM_SEGMENT_SIZE: usize = 64 * 1024;
+
+/// Nonce length for the STREAM construction over a 12-byte-nonce AEAD
+/// (the remaining 5 bytes hold the segment counter and last-segment flag)
+pub const STREAM_NONCE_LENGTH: usize = 7;
+
+/// Incremental encryption or decryption using the STREAM construction.
+///
+/// Plaintext is cut into fixed segments of `STREAM_SEGMENT_SIZE` bytes, each
+/// sealed with its own tag, so the output does not depend on how the data
+/// was split across `update` calls. A decryptor only returns a segment once
+/// its tag has been checked, and `finalize` fails if the stream was cut short.
+pub trait StreamingCipher {
+    /// Start a new stream with a `STREAM_NONCE_LENGTH`-byte nonce
+    fn init(&mut self, nonce: &[u8]) -> Result<()>;
+
+    /// Feed input, returning any completed output segments
+    fn update(&mut self, data: &[u8]) -> Result<Vec<u8>>;
+
+    /// Process the remaining buffered data as the last segment
+    fn finalize(&mut self) -> Result<Vec<u8>>;
 }
 
 #[cfg(test)]
@@ -79,7 +130,8 @@ mod tests {
 
         let result = EncryptionResult::deserialize(&data, iv_length).unwrap();
         assert_eq!(result.iv, vec![1, 2, 3, 4]);
-        assert_eq!(result.ciphertext, vec![]);
+        // serde_json's `PartialEq<Value> for u8` makes a bare `vec![]` ambiguous here
+        assert_eq!(result.ciphertext, Vec::<u8>::new());
     }
 
     #[test]
@@ -118,4 +170,36 @@ mod tests {
         assert_eq!(result.iv, cloned.iv);
         assert_eq!(result.ciphertext, cloned.ciphertext);
     }
+
+    /// A cipher implementing only the required methods
+    struct NullCipher;
+
+    impl Cipher for NullCipher {
+        fn encrypt(&self, plaintext: &[u8]) -> Result<EncryptionResult> {
+            Ok(EncryptionResult {
+                iv: Vec::new(),
+                ciphertext: plaintext.to_vec(),
+            })
+        }
+
+        fn decrypt(&self, _iv: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+            Ok(ciphertext.to_vec())
+        }
+
+        fn iv_length(&self) -> usize {
+            0
+        }
+
+        fn key_length(&self) -> usize {
+            0
+        }
+    }
+
+    #[test]
+    fn test_cipher_default_methods() {
+        assert!(NullCipher.encrypt_with_iv(&[], b"data").is_err());
+        assert!(NullCipher.streaming_encryptor().is_err());
+        assert!(NullCipher.streaming_decryptor().is_err());
+        assert_eq!(NullCipher.tag_length(), 16);
+    }
 }
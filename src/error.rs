@@ -16,6 +16,13 @@ pub enum RusWaCipherError {
     #[error("Decryption error: {0}")]
     Decryption(String),
 
+    /// The key or data did not pass an authenticity check (AEAD tag or signature)
+    #[error("Authentication failed: {0}")]
+    Authentication(String),
+
+    #[error("Unsupported algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+
     #[error("Key management error: {0}")]
     KeyManagement(String),
 
@@ -32,6 +39,27 @@ pub enum RusWaCipherError {
     Base64Decode(#[from] base64::DecodeError),
 }
 
+impl RusWaCipherError {
+    /// Process exit code for this error, so scripts can tell failure causes apart.
+    ///
+    /// `1` is reserved for unexpected failures and `2` for invalid arguments
+    /// (reported by the argument parser before any command runs).
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            RusWaCipherError::Io(_) => 3,
+            RusWaCipherError::Encryption(_) | RusWaCipherError::Decryption(_) => 4,
+            RusWaCipherError::WasmParser(_) => 5,
+            RusWaCipherError::KeyManagement(_)
+            | RusWaCipherError::HexDecode(_)
+            | RusWaCipherError::Base64Decode(_) => 6,
+            RusWaCipherError::InvalidInput(_) => 7,
+            RusWaCipherError::Config(_) => 8,
+            RusWaCipherError::Authentication(_) => 9,
+            RusWaCipherError::UnsupportedAlgorithm(_) => 10,
+        }
+    }
+}
+
 impl From<aes_gcm::Error> for RusWaCipherError {
     fn from(err: aes_gcm::Error) -> Self {
         RusWaCipherError::Encryption(format!("AES-GCM error: {:?}", err))
@@ -43,3 +71,42 @@ impl From<getrandom::Error> for RusWaCipherError {
         RusWaCipherError::KeyManagement(format!("Random generation error: {:?}", err))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes() {
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        let wasm = match wasmparser::Validator::new().validate_all(b"\0asm\x02\0\0\0") {
+            Err(e) => e,
+            Ok(_) => panic!("unsupported version must not validate"),
+        };
+
+        let cases = [
+            (RusWaCipherError::Io(io), 3),
+            (RusWaCipherError::Encryption("e".into()), 4),
+            (RusWaCipherError::Decryption("e".into()), 4),
+            (RusWaCipherError::WasmParser(wasm), 5),
+            (RusWaCipherError::KeyManagement("e".into()), 6),
+            (hex::decode("zz").unwrap_err().into(), 6),
+            (base64_error(), 6),
+            (RusWaCipherError::InvalidInput("e".into()), 7),
+            (RusWaCipherError::Config("e".into()), 8),
+            (RusWaCipherError::Authentication("e".into()), 9),
+            (RusWaCipherError::UnsupportedAlgorithm("e".into()), 10),
+        ];
+        for (error, code) in cases {
+            assert_eq!(error.exit_code(), code, "{}", error);
+        }
+    }
+
+    fn base64_error() -> RusWaCipherError {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD
+            .decode("!")
+            .unwrap_err()
+            .into()
+    }
+}
@@ -1,8 +1,15 @@
 use std::fs;
+use std::io::{Read, Write};
 use std::path::Path;
 
-use crate::error::Result;
-use base64::Engine;
+use crate::crypto::KeyManager;
+use crate::error::{Result, RusWaCipherError};
+
+/// Default chunk size for buffered reads (64 KiB)
+pub const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Default upper bound on a single frame's payload (64 MiB)
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 64 * 1024 * 1024;
 
 /// Read a file into a byte vector
 pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
@@ -10,33 +17,147 @@ pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
     Ok(data)
 }
 
+/// Read `reader` to the end in `buffer_size` chunks, calling `progress` with the
+/// running byte count after each chunk
+pub fn read_chunked<R: Read, F: FnMut(u64)>(
+    mut reader: R,
+    buffer_size: usize,
+    mut progress: F,
+) -> Result<Vec<u8>> {
+    if buffer_size == 0 {
+        return Err(RusWaCipherError::InvalidInput(
+            "Buffer size must be greater than zero".to_string(),
+        ));
+    }
+
+    let mut data = Vec::new();
+    let mut chunk = vec![0u8; buffer_size];
+    loop {
+        let n = match reader.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        data.extend_from_slice(&chunk[..n]);
+        progress(data.len() as u64);
+    }
+    Ok(data)
+}
+
+/// Read a file in `buffer_size` chunks, reporting progress like [`read_chunked`]
+pub fn read_file_with_progress<P: AsRef<Path>, F: FnMut(u64)>(
+    path: P,
+    buffer_size: usize,
+    progress: F,
+) -> Result<Vec<u8>> {
+    read_chunked(fs::File::open(path)?, buffer_size, progress)
+}
+
+/// Whether `path` is an `http://` or `https://` URL rather than a local file
+pub fn is_url(path: &Path) -> bool {
+    path.to_str()
+        .is_some_and(|s| s.starts_with("http://") || s.starts_with("https://"))
+}
+
+/// Download `url` into memory; `insecure` skips TLS certificate verification
+#[cfg(feature = "http")]
+pub fn fetch_url(url: &str, insecure: bool) -> Result<Vec<u8>> {
+    let fetch = || -> reqwest::Result<Vec<u8>> {
+        let client = reqwest::blocking::Client::builder()
+            .danger_accept_invalid_certs(insecure)
+            .build()?;
+        Ok(client
+            .get(url)
+            .send()?
+            .error_for_status()?
+            .bytes()?
+            .to_vec())
+    };
+    fetch().map_err(|e| RusWaCipherError::InvalidInput(format!("Cannot fetch {}: {}", url, e)))
+}
+
+/// Download `url` into memory (requires the `http` feature)
+#[cfg(not(feature = "http"))]
+pub fn fetch_url(_url: &str, _insecure: bool) -> Result<Vec<u8>> {
+    Err(RusWaCipherError::Config(
+        "HTTP input is not compiled in; rebuild with --features http".to_string(),
+    ))
+}
+
+/// URL of the detached signature for `url`: `.sig` appended to its path, keeping any query
+#[cfg(feature = "http")]
+pub fn signature_url(url: &str) -> Result<String> {
+    let mut url = reqwest::Url::parse(url)
+        .map_err(|e| RusWaCipherError::InvalidInput(format!("Invalid URL {}: {}", url, e)))?;
+    let path = format!("{}.sig", url.path());
+    url.set_path(&path);
+    Ok(url.into())
+}
+
+/// URL of the detached signature for `url`: `.sig` appended to its path, keeping any query
+#[cfg(not(feature = "http"))]
+pub fn signature_url(_url: &str) -> Result<String> {
+    Err(RusWaCipherError::Config(
+        "HTTP input is not compiled in; rebuild with --features http".to_string(),
+    ))
+}
+
 /// Write bytes to a file
 pub fn write_file<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<()> {
     fs::write(path, data)?;
     Ok(())
 }
 
+/// Write bytes to a file through a temporary file in the same directory, so
+/// `path` is either left untouched or fully replaced
+pub fn write_file_atomic<P: AsRef<Path>>(path: P, data: &[u8]) -> Result<()> {
+    let path = path.as_ref();
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(format!(".{}.tmp", std::process::id()));
+    let temp = std::path::PathBuf::from(temp);
+
+    if let Err(e) = fs::write(&temp, data).and_then(|()| fs::rename(&temp, path)) {
+        let _ = fs::remove_file(&temp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
 /// Read a key file and return the key bytes
+///
+/// Text files are decoded as hex, then base64, falling back to the trimmed
+/// text itself. Files that are not UTF-8, such as binary keys written with
+/// [`KeyFormat::Raw`](crate::cli::KeyFormat::Raw), are returned byte for byte,
+/// as are UTF-8 files whose size is a supported key length when decoding them
+/// would not produce one.
 pub fn read_key_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
-    let content = fs::read_to_string(path)?;
-    let content = content.trim();
+    let path = path.as_ref();
+    let content = fs::read(path).map_err(|e| {
+        RusWaCipherError::KeyManagement(format!("Cannot read key file {:?}: {}", path, e))
+    })?;
+
+    let text = match std::str::from_utf8(&content) {
+        Ok(text) => KeyManager::clean_key_text(text),
+        Err(_) => return Ok(content),
+    };
+    // Try to decode as hex first, then as base64, then take the text itself
+    let decoded = KeyManager::decode_hex_key(text)
+        .or_else(|_| KeyManager::decode_base64_key(text))
+        .unwrap_or_else(|_| text.as_bytes().to_vec());
 
-    // Try to decode as hex first, then as base64
-    if let Ok(key) = hex::decode(content) {
-        Ok(key)
-    } else if let Ok(key) = base64::engine::general_purpose::STANDARD.decode(content) {
-        Ok(key)
-    } else {
-        // Assume it's raw bytes
-        Ok(content.as_bytes().to_vec())
+    // A raw key that happens to be valid UTF-8 must keep its edge bytes
+    let fits = |length| !crate::crypto::engine::candidate_algorithms(length).is_empty();
+    if !fits(decoded.len()) && fits(content.len()) {
+        return Ok(content);
     }
+    Ok(decoded)
 }
 
 /// Write a key to a file in hex format
 pub fn write_key_file<P: AsRef<Path>>(path: P, key: &[u8]) -> Result<()> {
     let hex_key = hex::encode(key);
-    fs::write(path, hex_key)?;
-    Ok(())
+    write_secret_file(path.as_ref(), hex_key.as_bytes())
 }
 
 /// Write a key to a file in the specified format
@@ -52,20 +173,176 @@ pub fn write_key_file_with_format<P: AsRef<Path>>(
         }
         crate::cli::KeyFormat::Raw => {
             // For raw format, write binary data directly
-            fs::write(path, key)?;
-            return Ok(());
+            return write_secret_file(path.as_ref(), key);
         }
     };
 
-    fs::write(path, content)?;
+    write_secret_file(path.as_ref(), content.as_bytes())
+}
+
+/// Write key material readable only by the owner (mode 0600 on Unix)
+#[cfg(unix)]
+fn write_secret_file(path: &Path, data: &[u8]) -> Result<()> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+
+    // The mode only applies to newly created files, so tighten existing ones too
+    if let Err(e) = file.set_permissions(fs::Permissions::from_mode(0o600)) {
+        log::warn!(
+            "Could not restrict permissions on key file {:?}: {}",
+            path,
+            e
+        );
+    }
+
+    file.write_all(data)?;
     Ok(())
 }
 
+/// Write key material readable only by the owner (mode 0600 on Unix)
+#[cfg(not(unix))]
+fn write_secret_file(path: &Path, data: &[u8]) -> Result<()> {
+    fs::write(path, data)?;
+    log::warn!(
+        "Key file {:?} uses default permissions; restrict access to it manually",
+        path
+    );
+    Ok(())
+}
+
+/// Writes `[u32 length][payload]` records to an underlying writer.
+///
+/// The length prefix is little-endian, matching the rest of the container format.
+pub struct FrameWriter<W: Write> {
+    inner: W,
+}
+
+impl<W: Write> FrameWriter<W> {
+    pub fn new(inner: W) -> Self {
+        FrameWriter { inner }
+    }
+
+    /// Write a single frame
+    pub fn write_frame(&mut self, payload: &[u8]) -> Result<()> {
+        let length = u32::try_from(payload.len()).map_err(|_| {
+            RusWaCipherError::InvalidInput(format!(
+                "Frame payload too large: {} bytes",
+                payload.len()
+            ))
+        })?;
+
+        self.inner.write_all(&length.to_le_bytes())?;
+        self.inner.write_all(payload)?;
+        Ok(())
+    }
+
+    /// Flush and return the underlying writer
+    pub fn into_inner(mut self) -> Result<W> {
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+/// Reads `[u32 length][payload]` records written by [`FrameWriter`].
+pub struct FrameReader<R: Read> {
+    inner: R,
+    max_frame_size: usize,
+}
+
+impl<R: Read> FrameReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_max_frame_size(inner, DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    /// Create a reader that rejects frames declaring more than `max_frame_size` bytes
+    pub fn with_max_frame_size(inner: R, max_frame_size: usize) -> Self {
+        FrameReader {
+            inner,
+            max_frame_size,
+        }
+    }
+
+    /// Read the next frame, returning `None` at a clean end of stream
+    pub fn read_frame(&mut self) -> Result<Option<Vec<u8>>> {
+        let mut length_bytes = [0u8; 4];
+        let mut filled = 0;
+        while filled < length_bytes.len() {
+            match self.inner.read(&mut length_bytes[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => {
+                    return Err(RusWaCipherError::InvalidInput(
+                        "Truncated frame length prefix".to_string(),
+                    ))
+                }
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let length = u32::from_le_bytes(length_bytes) as usize;
+        if length > self.max_frame_size {
+            return Err(RusWaCipherError::InvalidInput(format!(
+                "Frame length {} exceeds maximum of {} bytes",
+                length, self.max_frame_size
+            )));
+        }
+
+        // Read through `take` so a bogus length can't trigger a large allocation
+        let mut payload = Vec::new();
+        (&mut self.inner)
+            .take(length as u64)
+            .read_to_end(&mut payload)?;
+        if payload.len() != length {
+            return Err(RusWaCipherError::InvalidInput(format!(
+                "Truncated frame: declared {} bytes, got {}",
+                length,
+                payload.len()
+            )));
+        }
+
+        Ok(Some(payload))
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::NamedTempFile;
 
+    #[cfg(unix)]
+    #[test]
+    fn test_key_files_are_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let hex_path = temp_dir.path().join("hex.key");
+        let raw_path = temp_dir.path().join("raw.key");
+
+        // An existing world-readable file must be tightened as well
+        fs::write(&raw_path, b"old").unwrap();
+        fs::set_permissions(&raw_path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_key_file(&hex_path, &[1u8; 32]).unwrap();
+        write_key_file_with_format(&raw_path, &[2u8; 32], &crate::cli::KeyFormat::Raw).unwrap();
+
+        for path in [&hex_path, &raw_path] {
+            let mode = fs::metadata(path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        assert_eq!(fs::read(&raw_path).unwrap(), vec![2u8; 32]);
+    }
+
     #[test]
     fn test_read_write_file() {
         let temp_file = NamedTempFile::new().unwrap();
@@ -127,6 +404,51 @@ mod tests {
         assert_eq!(content, test_key);
     }
 
+    #[test]
+    fn test_raw_key_file_round_trip() {
+        let temp_file = NamedTempFile::new().unwrap();
+        // Invalid UTF-8 with whitespace at both ends, which text handling would mangle
+        let mut test_key = vec![b'\n', 0xff, 0x00, 0xfe];
+        test_key.extend((0u8..27).map(|b| b.wrapping_mul(37)));
+        test_key.push(b' ');
+
+        write_key_file_with_format(temp_file.path(), &test_key, &crate::cli::KeyFormat::Raw)
+            .unwrap();
+        assert_eq!(read_key_file(temp_file.path()).unwrap(), test_key);
+    }
+
+    #[test]
+    fn test_read_key_file_trims_raw_text_key() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let passphrase = "correct horse battery staple !!!";
+
+        std::fs::write(temp_file.path(), format!("{}\n", passphrase)).unwrap();
+        assert_eq!(
+            read_key_file(temp_file.path()).unwrap(),
+            passphrase.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_read_key_file_keeps_utf8_raw_key_whitespace() {
+        let temp_file = NamedTempFile::new().unwrap();
+        // A 32-byte raw key that is valid UTF-8 with whitespace at both ends
+        let test_key = b"\t raw key bytes, valid utf-8! \r\n";
+        assert_eq!(test_key.len(), 32);
+
+        write_key_file_with_format(temp_file.path(), test_key, &crate::cli::KeyFormat::Raw)
+            .unwrap();
+        assert_eq!(read_key_file(temp_file.path()).unwrap(), test_key);
+
+        // Text that decodes to a supported length still wins over its own size
+        let hex_digits = b"0123456789abcdef0123456789abcdef";
+        std::fs::write(temp_file.path(), hex_digits).unwrap();
+        assert_eq!(
+            read_key_file(temp_file.path()).unwrap(),
+            hex::decode(hex_digits).unwrap()
+        );
+    }
+
     #[test]
     fn test_read_key_file_hex_format() {
         let temp_file = NamedTempFile::new().unwrap();
@@ -151,4 +473,118 @@ mod tests {
 
         assert_eq!(read_key, test_key);
     }
+
+    #[test]
+    fn test_read_key_file_with_bom_and_hex_prefix() {
+        let test_key = b"test_key_12345678901234567890123";
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let base64_content =
+            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, test_key);
+        std::fs::write(temp_file.path(), format!("\u{FEFF}{}\r\n", base64_content)).unwrap();
+        assert_eq!(read_key_file(temp_file.path()).unwrap(), test_key);
+
+        let temp_file = NamedTempFile::new().unwrap();
+        std::fs::write(temp_file.path(), format!("0x{}\n", hex::encode(test_key))).unwrap();
+        assert_eq!(read_key_file(temp_file.path()).unwrap(), test_key);
+    }
+
+    #[test]
+    fn test_frame_round_trip() {
+        let frames: Vec<&[u8]> = vec![b"first", b"", b"third frame payload"];
+
+        let mut writer = FrameWriter::new(Vec::new());
+        for frame in &frames {
+            writer.write_frame(frame).unwrap();
+        }
+        let buffer = writer.into_inner().unwrap();
+
+        let mut reader = FrameReader::new(std::io::Cursor::new(buffer));
+        for frame in &frames {
+            assert_eq!(reader.read_frame().unwrap().unwrap(), *frame);
+        }
+        assert!(reader.read_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_frame_declared_length_exceeds_data() {
+        let mut buffer = 100u32.to_le_bytes().to_vec();
+        buffer.extend_from_slice(b"short");
+
+        let mut reader = FrameReader::new(std::io::Cursor::new(buffer));
+        let err = reader.read_frame().unwrap_err();
+        assert!(err.to_string().contains("Truncated frame"));
+    }
+
+    #[test]
+    fn test_frame_exceeds_max_size() {
+        let mut writer = FrameWriter::new(Vec::new());
+        writer.write_frame(&[0u8; 32]).unwrap();
+        let buffer = writer.into_inner().unwrap();
+
+        let mut reader = FrameReader::with_max_frame_size(std::io::Cursor::new(buffer), 16);
+        assert!(reader.read_frame().is_err());
+    }
+
+    /// Fails every other read with `Interrupted`, like a reader hit by signals
+    struct InterruptingReader<R> {
+        inner: R,
+        interrupt: bool,
+    }
+
+    impl<R: Read> Read for InterruptingReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+            self.inner.read(&mut buf[..1])
+        }
+    }
+
+    #[test]
+    fn test_frame_reader_retries_interrupted_reads() {
+        let mut data = Vec::new();
+        FrameWriter::new(&mut data).write_frame(b"frame").unwrap();
+
+        let mut reader = FrameReader::new(InterruptingReader {
+            inner: std::io::Cursor::new(data),
+            interrupt: false,
+        });
+        assert_eq!(reader.read_frame().unwrap(), Some(b"frame".to_vec()));
+        assert_eq!(reader.read_frame().unwrap(), None);
+    }
+
+    #[test]
+    fn test_frame_truncated_length_prefix() {
+        let mut reader = FrameReader::new(std::io::Cursor::new(vec![0x01, 0x00]));
+        assert!(reader.read_frame().is_err());
+    }
+
+    #[test]
+    fn test_read_chunked_reports_each_chunk() {
+        let data = vec![7u8; 95];
+        let mut reports = Vec::new();
+
+        let read = read_chunked(std::io::Cursor::new(&data), 10, |n| reports.push(n)).unwrap();
+
+        assert_eq!(read, data);
+        assert_eq!(reports.len(), 10);
+        assert_eq!(reports.first(), Some(&10));
+        assert_eq!(reports.last(), Some(&95));
+        assert!(read_chunked(std::io::Cursor::new(&data), 0, |_| {}).is_err());
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_signature_url_appends_to_path() {
+        assert_eq!(
+            signature_url("https://cdn.example.com/app.wasm").unwrap(),
+            "https://cdn.example.com/app.wasm.sig"
+        );
+        assert_eq!(
+            signature_url("https://cdn.example.com/app.wasm?v=3").unwrap(),
+            "https://cdn.example.com/app.wasm.sig?v=3"
+        );
+    }
 }
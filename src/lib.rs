@@ -3,6 +3,7 @@ pub mod config;
 pub mod crypto;
 pub mod error;
 pub mod io;
+pub mod timing;
 pub mod wasm;
 
 pub use error::{Result, RusWaCipherError};
@@ -1,157 +1,486 @@
 use clap::Parser;
-use log::{error, info};
+use log::{error, info, warn};
+use serde::Serialize;
+use std::io::IsTerminal;
+use std::path::Path;
 
 use ruswacipher::{
-    cli::{Cli, Commands},
-    config::EncryptionAlgorithm,
-    crypto::{key_management, AesGcmCipher, ChaCha20Poly1305Cipher, Cipher, EncryptionResult},
-    error::Result,
-    io::{read_file, read_key_file, write_file, write_key_file_with_format},
-    wasm::WasmParser,
+    cli::{self, Cli, Commands, OutputFormat},
+    crypto::{self, embedded_key, key_management},
+    error::{Result, RusWaCipherError},
+    io::{
+        fetch_url, is_url, read_file_with_progress, read_key_file, signature_url, write_file,
+        write_key_file_with_format,
+    },
+    timing::PhaseTimer,
+    wasm::{self, envelope, transform, ObfuscationContext, Transform, WasmParser, WasmWriter},
 };
 
 fn main() {
     let cli = Cli::parse();
 
     // Initialize logger
-    if cli.verbose {
-        env_logger::Builder::from_default_env()
-            .filter_level(log::LevelFilter::Debug)
-            .init();
-    } else {
-        env_logger::Builder::from_default_env()
-            .filter_level(log::LevelFilter::Info)
-            .init();
+    env_logger::Builder::from_default_env()
+        .filter_level(cli.log_level())
+        .init();
+
+    if let Some(path) = &cli.audit_log {
+        match crypto::jsonl_file_sink(path) {
+            Ok(sink) => crypto::set_audit_sink(sink),
+            Err(e) => {
+                error!("Error: cannot open audit log {:?}: {}", path, e);
+                std::process::exit(e.exit_code());
+            }
+        }
     }
 
+    // Keep stdout clean for pipes and JSON consumers
+    let show_progress = cli.format == OutputFormat::Text && std::io::stdout().is_terminal();
+
     let result = match &cli.command {
-        Commands::Encrypt { .. } => handle_encrypt(&cli.command),
-        Commands::Decrypt { .. } => handle_decrypt(&cli.command),
+        Commands::Encrypt { .. } => handle_encrypt(&cli.command, show_progress)
+            .inspect(|report| {
+                if let (OutputFormat::Text, Some(sri)) = (cli.format, &report.integrity) {
+                    println!("{}", sri);
+                }
+            })
+            .map(CommandReport::Encrypt),
+        Commands::Decrypt { .. } => {
+            handle_decrypt(&cli.command, show_progress).map(CommandReport::Decrypt)
+        }
+        Commands::Version => {
+            if cli.format == OutputFormat::Text {
+                println!("{}", cli::version_string());
+            }
+            Ok(CommandReport::Version(VersionReport {
+                version: env!("CARGO_PKG_VERSION"),
+                commit: cli::GIT_COMMIT,
+                build_date: cli::BUILD_DATE,
+            }))
+        }
+        Commands::Completions { shell } => {
+            cli::write_completions(*shell, &mut std::io::stdout());
+            return;
+        }
     };
 
-    if let Err(e) = result {
-        error!("Error: {}", e);
-        std::process::exit(1);
+    match result {
+        Ok(report) => {
+            if cli.format == OutputFormat::Json {
+                match serde_json::to_string(&report) {
+                    Ok(json) => println!("{}", json),
+                    Err(e) => {
+                        error!("Error: failed to serialize report: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            error!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
     }
 }
 
-fn handle_encrypt(command: &Commands) -> Result<()> {
+/// Result of a command, printed to stdout with `--format json`
+#[derive(Serialize)]
+#[serde(tag = "command", rename_all = "lowercase")]
+enum CommandReport {
+    Encrypt(EncryptReport),
+    Decrypt(DecryptReport),
+    Version(VersionReport),
+}
+
+#[derive(Serialize)]
+struct EncryptReport {
+    algorithm: String,
+    input: String,
+    output: String,
+    input_size: usize,
+    output_size: usize,
+    /// Hex-encoded key, only present when a new key was generated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generated_key: Option<String>,
+    obfuscation: Vec<String>,
+    /// Post-MVP WASM features the module needs (`simd`, `threads`), for loader gating
+    features: Vec<String>,
+    /// SRI hash of the output, only present with --print-sri
+    #[serde(skip_serializing_if = "Option::is_none")]
+    integrity: Option<String>,
+}
+
+#[derive(Serialize)]
+struct VersionReport {
+    version: &'static str,
+    commit: &'static str,
+    build_date: &'static str,
+}
+
+#[derive(Serialize)]
+struct DecryptReport {
+    input: String,
+    output: String,
+    input_size: usize,
+    output_size: usize,
+}
+
+/// Read the input file, drawing a progress bar on stderr when enabled
+#[cfg(feature = "progress")]
+fn read_input(path: &Path, buffer_size: usize, show_progress: bool) -> Result<Vec<u8>> {
+    let bar = if show_progress {
+        indicatif::ProgressBar::new(std::fs::metadata(path)?.len())
+    } else {
+        indicatif::ProgressBar::hidden()
+    };
+    if let Ok(style) =
+        indicatif::ProgressStyle::with_template("{msg} [{bar:40}] {bytes}/{total_bytes} ({eta})")
+    {
+        bar.set_style(style.progress_chars("=> "));
+    }
+    bar.set_message(format!("Reading {}", path.display()));
+
+    let data = read_file_with_progress(path, buffer_size, |n| bar.set_position(n));
+    bar.finish_and_clear();
+    data
+}
+
+/// Read the input file (build with `--features progress` for a progress bar)
+#[cfg(not(feature = "progress"))]
+fn read_input(path: &Path, buffer_size: usize, _show_progress: bool) -> Result<Vec<u8>> {
+    read_file_with_progress(path, buffer_size, |_| {})
+}
+
+/// Notice text for `--notice`: the contents of the named file, or the value itself
+fn load_notice(value: &str) -> Result<String> {
+    let path = Path::new(value);
+    if path.is_file() {
+        Ok(std::fs::read_to_string(path)?)
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+fn handle_encrypt(command: &Commands, show_progress: bool) -> Result<EncryptReport> {
     let config = command.to_encryption_config()?;
+    let mut timer = PhaseTimer::new();
 
     info!("Reading WASM file: {:?}", config.input_file);
-    let wasm_data = read_file(&config.input_file)?;
+    let mut wasm_data = timer.time("read", || {
+        read_input(&config.input_file, config.buffer_size, show_progress)
+    })?;
+    let input_size = wasm_data.len();
+
+    if config.wat {
+        info!("Assembling WAT input...");
+        wasm_data = timer.time("assemble", || {
+            let source = String::from_utf8(wasm_data.clone()).map_err(|_| {
+                RusWaCipherError::InvalidInput("WAT input is not valid UTF-8".to_string())
+            })?;
+            WasmParser::assemble_wat(&source, &config.input_file)
+        })?;
+    }
 
     info!("Validating WASM file...");
-    WasmParser::validate_wasm(&wasm_data)?;
+    timer.time("parse", || WasmParser::validate_wasm(&wasm_data))?;
+
+    let features = WasmParser::detect_features(&wasm_data)?;
+    if !features.is_empty() {
+        info!("Module requires WASM features: {}", features.join(", "));
+    }
+
+    let mut transforms: Vec<Box<dyn Transform>> = Vec::new();
+    if config.strip_only {
+        transforms.push(Box::new(transform::StripMetadata));
+    }
+    if config.scramble_names || config.obfuscate_globals {
+        if wasm_data.len() < config.min_obfuscation_size {
+            info!(
+                "Module is {} bytes, below --min-obfuscation-size {}; skipping obfuscation passes",
+                wasm_data.len(),
+                config.min_obfuscation_size
+            );
+        } else {
+            if config.scramble_names {
+                transforms.push(Box::new(transform::ScrambleNames));
+            }
+            if config.obfuscate_globals {
+                transforms.push(Box::new(transform::ObfuscateGlobals));
+            }
+        }
+    }
+
+    let mut ctx = ObfuscationContext {
+        preserve: config.preserve.clone(),
+        ..Default::default()
+    };
+    if !transforms.is_empty() {
+        let original = timer.time("decode", || WasmParser::parse_module(&wasm_data))?;
+        let module = wasm::run_transforms(original.clone(), &transforms, &mut ctx)?;
+        // One phase per pass, so -v shows which pass is slow
+        timer.extend(std::mem::take(&mut ctx.timer));
+        if !ctx.applied.is_empty() {
+            wasm_data = timer.time("encode", || WasmWriter::serialize_module(&module))?;
+        }
+
+        if config.show_diff {
+            let changes = wasm::diff(&original, &module);
+            if changes.is_empty() {
+                info!("Diff: no changes");
+            }
+            for change in changes {
+                info!("Diff: {}", change);
+            }
+        }
+    } else if config.show_diff {
+        info!("Diff: no obfuscation pass ran; the module is unchanged");
+    }
+
+    let notice = config.notice.as_deref().map(load_notice).transpose()?;
+    if let Some(notice) = &notice {
+        info!("Attaching notice section");
+        let mut module = WasmParser::parse_module(&wasm_data)?;
+        module.set_notice(notice);
+        wasm_data = WasmWriter::serialize_module(&module)?;
+    }
+
+    if let Some(path) = &config.dump_wat {
+        info!("Writing WAT dump: {:?}", path);
+        let text = WasmParser::parse_module(&wasm_data)?.to_wat()?;
+        write_file(path, text.as_bytes())?;
+    }
 
     // Get or generate key
+    let mut generated = false;
     let key = if let Some(key) = key_management::resolve_key(&config)? {
         info!("Using provided key");
         key
     } else {
+        generated = true;
         info!("Generating new key...");
         let key = key_management::generate_key(&config.algorithm)?;
 
-        if let Some(key_output_file) = &config.key_output_file {
+        let keyring_entry = config
+            .keyring_service
+            .as_deref()
+            .zip(config.keyring_account.as_deref());
+
+        // A key generated into the keyring is only written to a file on request
+        let key_output_file = match (&config.key_output_file, keyring_entry) {
+            (Some(path), _) => Some(path.clone()),
+            (None, Some(_)) => None,
+            (None, None) => Some(key_management::default_key_path(&config.output_file)),
+        };
+        if let Some(key_output_file) = &key_output_file {
+            if key_output_file.exists() && !config.force_key {
+                return Err(RusWaCipherError::KeyManagement(format!(
+                    "Key file {:?} already exists and may protect another file; \
+                     pass --force-key to overwrite it or --generate-key <PATH> to choose another path",
+                    key_output_file
+                )));
+            }
+
             info!("Saving key to file: {:?}", key_output_file);
             write_key_file_with_format(key_output_file, &key, &config.key_format)?;
+        }
 
-            // Also print the key to console for user convenience
-            let key_display = key_management::KeyManager::encode_key(&key, &config.key_format);
-            if matches!(config.key_format, ruswacipher::cli::KeyFormat::Raw) {
-                info!("Generated key: {}", key_display);
-            } else {
-                info!(
-                    "Generated key ({}): {}",
-                    match config.key_format {
-                        ruswacipher::cli::KeyFormat::Hex => "hex",
-                        ruswacipher::cli::KeyFormat::Base64 => "base64",
-                        ruswacipher::cli::KeyFormat::Raw => "raw",
-                    },
-                    key_display
-                );
-            }
+        // Also print the key to console for user convenience
+        let key_display = key_management::KeyManager::encode_key(&key, &config.key_format);
+        if matches!(config.key_format, ruswacipher::cli::KeyFormat::Raw) {
+            info!("Generated key: {}", key_display);
+        } else {
+            info!(
+                "Generated key ({}): {}",
+                match config.key_format {
+                    ruswacipher::cli::KeyFormat::Hex => "hex",
+                    ruswacipher::cli::KeyFormat::Base64 => "base64",
+                    ruswacipher::cli::KeyFormat::Raw => "raw",
+                },
+                key_display
+            );
+        }
+
+        if let Some((service, account)) = keyring_entry {
+            info!("Storing key in keyring entry {}/{}", service, account);
+            key_management::store_keyring_key(service, account, &key)?;
         }
 
         key
     };
 
-    // Encrypt based on algorithm
-    let encrypted_data = match config.algorithm {
-        EncryptionAlgorithm::AesGcm => {
-            info!("Encrypting with AES-GCM...");
-            let cipher = AesGcmCipher::new(&key)?;
-            let result = cipher.encrypt(&wasm_data)?;
-            result.serialize()
+    if config.convergent {
+        warn!("Convergent mode: identical modules encrypted with the same key produce identical output");
+    }
+
+    let mut encrypted_data = timer.time("encrypt", || {
+        if config.convergent {
+            crypto::encrypt_data_convergent(&wasm_data, &key, &config.algorithm)
+        } else {
+            crypto::encrypt_data(&wasm_data, &key, &config.algorithm)
         }
-        EncryptionAlgorithm::ChaCha20Poly1305 => {
-            info!("Encrypting with ChaCha20-Poly1305...");
-            let cipher = ChaCha20Poly1305Cipher::new(&key)?;
-            let result = cipher.encrypt(&wasm_data)?;
-            result.serialize()
+    })?;
+
+    if config.embed_key {
+        warn!("WARNING: --embed-key stores the key inside the output file.");
+        warn!("WARNING: Anyone who obtains the file can decrypt it; protect it in transport.");
+        encrypted_data = embedded_key::append_key_trailer(&encrypted_data, &key)?;
+    }
+
+    if config.envelope {
+        info!("Wrapping encrypted data in a WASM envelope");
+        encrypted_data = envelope::wrap_payload(&encrypted_data, notice.as_deref())?;
+    }
+
+    if let Some(split_size) = config.split_size {
+        let parts = crypto::parts::split_parts(&encrypted_data, split_size)?;
+        info!(
+            "Writing encrypted file as {} parts: {:?}",
+            parts.len(),
+            crypto::parts::part_path(&config.output_file, 0)
+        );
+        timer.time("write", || {
+            parts.iter().enumerate().try_for_each(|(index, part)| {
+                write_file(crypto::parts::part_path(&config.output_file, index), part)
+            })
+        })?;
+
+        // Parts left over from an earlier, larger split would break reassembly
+        let stale = (parts.len()..)
+            .map(|index| crypto::parts::part_path(&config.output_file, index))
+            .take_while(|path| path.exists());
+        for path in stale {
+            info!("Removing stale part: {:?}", path);
+            std::fs::remove_file(path)?;
         }
-    };
+    } else {
+        info!("Writing encrypted file: {:?}", config.output_file);
+        timer.time("write", || write_file(&config.output_file, &encrypted_data))?;
+    }
 
-    info!("Writing encrypted file: {:?}", config.output_file);
-    write_file(&config.output_file, &encrypted_data)?;
+    if let Some(sign_key) = &config.sign_key {
+        let signature =
+            crypto::signing::sign_container(&encrypted_data, &read_key_file(sign_key)?)?;
+        let signature_file = crypto::signing::signature_path(&config.output_file);
+        info!("Writing Ed25519 signature: {:?}", signature_file);
+        write_file(&signature_file, &signature)?;
+    }
 
     info!("Encryption completed successfully!");
     info!("Original size: {} bytes", wasm_data.len());
     info!("Encrypted size: {} bytes", encrypted_data.len());
+    info!("Timing: {}", timer.summary());
 
-    Ok(())
+    Ok(EncryptReport {
+        algorithm: config.algorithm.canonical_name().to_string(),
+        input: config.input_file.display().to_string(),
+        output: config.output_file.display().to_string(),
+        input_size,
+        output_size: encrypted_data.len(),
+        generated_key: generated.then(|| hex::encode(&key)),
+        obfuscation: ctx.applied,
+        features: features.iter().map(|f| f.to_string()).collect(),
+        integrity: config
+            .print_sri
+            .map(|algorithm| crypto::sri_hash(algorithm, &encrypted_data)),
+    })
 }
 
-fn handle_decrypt(command: &Commands) -> Result<()> {
+fn handle_decrypt(command: &Commands, show_progress: bool) -> Result<DecryptReport> {
     let config = command.to_decryption_config()?;
+    let mut timer = PhaseTimer::new();
+
+    let url = is_url(&config.input_file).then(|| config.input_file.to_string_lossy());
+    if config.insecure {
+        if url.is_none() {
+            return Err(RusWaCipherError::InvalidInput(
+                "--insecure only applies to http:// or https:// inputs".to_string(),
+            ));
+        }
+        warn!("WARNING: --insecure disables TLS certificate verification");
+    }
 
     info!("Reading encrypted file: {:?}", config.input_file);
-    let encrypted_data = read_file(&config.input_file)?;
+    let mut encrypted_data = timer.time("read", || match &url {
+        Some(_) if config.parts => Err(RusWaCipherError::InvalidInput(
+            "--parts reads local files and cannot be used with a URL input".to_string(),
+        )),
+        Some(url) => fetch_url(url, config.insecure),
+        None if config.parts => crypto::parts::read_parts(&config.input_file),
+        None => read_input(&config.input_file, config.buffer_size, show_progress),
+    })?;
+    let input_size = encrypted_data.len();
+
+    if let Some(verify_key) = &config.verify_key {
+        let signature_file = crypto::signing::signature_path(&config.input_file);
+        info!("Verifying Ed25519 signature: {:?}", signature_file);
+        let signature = match &url {
+            Some(url) => fetch_url(&signature_url(url)?, config.insecure)?,
+            None => std::fs::read(&signature_file).map_err(|e| {
+                RusWaCipherError::InvalidInput(format!(
+                    "Cannot read signature file {:?}: {}",
+                    signature_file, e
+                ))
+            })?,
+        };
+        crypto::signing::verify_container(
+            &encrypted_data,
+            &signature,
+            &read_key_file(verify_key)?,
+        )?;
+    }
 
-    info!("Reading key from file: {:?}", config.key_file);
-    let key = read_key_file(&config.key_file)?;
+    if let Some(payload) = envelope::unwrap_payload(&encrypted_data)? {
+        info!("Extracting payload from WASM envelope");
+        encrypted_data = payload;
+    }
 
-    // Try to decrypt with different algorithms
-    // First try AES-GCM
-    let decrypted_data = if let Ok(cipher) = AesGcmCipher::new(&key) {
-        info!("Attempting decryption with AES-GCM...");
-        let encryption_result = EncryptionResult::deserialize(&encrypted_data, cipher.iv_length())?;
+    let (encrypted_data, embedded) = match embedded_key::split_key_trailer(&encrypted_data)? {
+        Some((container, key)) => (container, Some(key)),
+        None => (encrypted_data.as_slice(), None),
+    };
 
-        match cipher.decrypt(&encryption_result.iv, &encryption_result.ciphertext) {
-            Ok(data) => {
-                info!("Successfully decrypted with AES-GCM");
-                data
-            }
-            Err(_) => {
-                // Try ChaCha20-Poly1305
-                info!("AES-GCM decryption failed, trying ChaCha20-Poly1305...");
-                let cipher = ChaCha20Poly1305Cipher::new(&key)?;
-                let encryption_result =
-                    EncryptionResult::deserialize(&encrypted_data, cipher.iv_length())?;
-                let data = cipher.decrypt(&encryption_result.iv, &encryption_result.ciphertext)?;
-                info!("Successfully decrypted with ChaCha20-Poly1305");
-                data
-            }
-        }
+    let key = if let Some(key) = key_management::resolve_decryption_key(&config)? {
+        key
+    } else if let Some(key) = embedded {
+        warn!("Using key embedded in the encrypted file");
+        key
     } else {
-        // Try ChaCha20-Poly1305 if AES-GCM key creation failed
-        info!("Attempting decryption with ChaCha20-Poly1305...");
-        let cipher = ChaCha20Poly1305Cipher::new(&key)?;
-        let encryption_result = EncryptionResult::deserialize(&encrypted_data, cipher.iv_length())?;
-        let data = cipher.decrypt(&encryption_result.iv, &encryption_result.ciphertext)?;
-        info!("Successfully decrypted with ChaCha20-Poly1305");
-        data
+        return Err(RusWaCipherError::KeyManagement(
+            "No key provided: pass --key or encrypt with --embed-key".to_string(),
+        ));
     };
 
+    // Try each algorithm compatible with the key length
+    let mut decrypted_data =
+        timer.time("decrypt", || crypto::decrypt_data(encrypted_data, &key))?;
+
     info!("Validating decrypted WASM file...");
-    WasmParser::validate_wasm(&decrypted_data)?;
+    timer.time("parse", || WasmParser::validate_wasm(&decrypted_data))?;
+
+    if config.clean {
+        timer.time("clean", || -> Result<()> {
+            let mut module = WasmParser::parse_module(&decrypted_data)?;
+            let removed = module.strip_custom_sections(wasm::RESERVED_SECTION_PREFIX);
+            if removed > 0 {
+                info!("Removed {} RusWaCipher custom section(s)", removed);
+                decrypted_data = WasmWriter::serialize_module(&module)?;
+            }
+            Ok(())
+        })?;
+    }
 
     info!("Writing decrypted file: {:?}", config.output_file);
-    write_file(&config.output_file, &decrypted_data)?;
+    timer.time("write", || write_file(&config.output_file, &decrypted_data))?;
 
     info!("Decryption completed successfully!");
     info!("Encrypted size: {} bytes", encrypted_data.len());
     info!("Decrypted size: {} bytes", decrypted_data.len());
+    info!("Timing: {}", timer.summary());
 
-    Ok(())
+    Ok(DecryptReport {
+        input: config.input_file.display().to_string(),
+        output: config.output_file.display().to_string(),
+        input_size,
+        output_size: decrypted_data.len(),
+    })
 }
@@ -0,0 +1,82 @@
+use std::time::{Duration, Instant};
+
+/// Records how long each phase of an operation took
+#[derive(Debug, Default)]
+pub struct PhaseTimer {
+    phases: Vec<(String, Duration)>,
+}
+
+impl PhaseTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f` and record its elapsed time under `phase`
+    pub fn time<T>(&mut self, phase: &str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.record(phase, start.elapsed());
+        result
+    }
+
+    /// Record a phase timed elsewhere
+    pub fn record(&mut self, phase: &str, elapsed: Duration) {
+        self.phases.push((phase.to_string(), elapsed));
+    }
+
+    /// Append the phases recorded by `other`, e.g. a sub-operation's own timer
+    pub fn extend(&mut self, other: PhaseTimer) {
+        self.phases.extend(other.phases);
+    }
+
+    /// Recorded phases in the order they ran
+    pub fn phases(&self) -> &[(String, Duration)] {
+        &self.phases
+    }
+
+    /// Total time across all recorded phases
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|(_, elapsed)| *elapsed).sum()
+    }
+
+    /// One-line breakdown, e.g. `parse 0.12ms, encrypt 0.40ms (total 0.52ms)`
+    pub fn summary(&self) -> String {
+        let phases: Vec<String> = self
+            .phases
+            .iter()
+            .map(|(name, elapsed)| format!("{} {:.2}ms", name, millis(*elapsed)))
+            .collect();
+
+        format!(
+            "{} (total {:.2}ms)",
+            phases.join(", "),
+            millis(self.total())
+        )
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_timer_records_phases() {
+        let mut timer = PhaseTimer::new();
+        let value = timer.time("parse", || 42);
+        timer.time("encrypt", || std::thread::sleep(Duration::from_millis(1)));
+
+        assert_eq!(value, 42);
+        let names: Vec<&str> = timer.phases().iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["parse", "encrypt"]);
+        assert!(timer.total() >= Duration::from_millis(1));
+
+        let summary = timer.summary();
+        assert!(summary.starts_with("parse "));
+        assert!(summary.contains("encrypt "));
+        assert!(summary.contains("total"));
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use crate::wasm::leb::LebDecoder;
+use crate::wasm::module::{Section, SectionType, WasmModule};
+
+/// A single difference between two versions of a module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionDiff {
+    /// Section present only in the second module
+    Added { section: String, size: usize },
+    /// Section present only in the first module
+    Removed { section: String, size: usize },
+    /// Section whose payload size changed
+    Resized {
+        section: String,
+        before: usize,
+        after: usize,
+    },
+    /// Section with the same size but different content
+    Modified { section: String, size: usize },
+    /// Function body (by position in the Code section) that changed
+    FunctionBody {
+        index: usize,
+        before: usize,
+        after: usize,
+    },
+}
+
+impl std::fmt::Display for SectionDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SectionDiff::Added { section, size } => {
+                write!(f, "+ {} section ({} bytes)", section, size)
+            }
+            SectionDiff::Removed { section, size } => {
+                write!(f, "- {} section ({} bytes)", section, size)
+            }
+            SectionDiff::Resized {
+                section,
+                before,
+                after,
+            } => write!(f, "~ {} section: {} -> {} bytes", section, before, after),
+            SectionDiff::Modified { section, size } => {
+                write!(f, "~ {} section: content changed ({} bytes)", section, size)
+            }
+            SectionDiff::FunctionBody {
+                index,
+                before,
+                after,
+            } => write!(
+                f,
+                "~ function body {}: {} -> {} bytes",
+                index, before, after
+            ),
+        }
+    }
+}
+
+/// Report which sections and function bodies differ between two modules
+pub fn diff(before: &WasmModule, after: &WasmModule) -> Vec<SectionDiff> {
+    let before_sections = keyed_sections(before);
+    let after_sections = keyed_sections(after);
+    let mut diffs = Vec::new();
+
+    for (key, old) in &before_sections {
+        match after_sections.iter().find(|(k, _)| k == key) {
+            None => diffs.push(SectionDiff::Removed {
+                section: section_label(old),
+                size: old.data.len(),
+            }),
+            Some((_, new)) => {
+                if old.data.len() != new.data.len() {
+                    diffs.push(SectionDiff::Resized {
+                        section: section_label(old),
+                        before: old.data.len(),
+                        after: new.data.len(),
+                    });
+                } else if old.data != new.data {
+                    diffs.push(SectionDiff::Modified {
+                        section: section_label(old),
+                        size: old.data.len(),
+                    });
+                }
+
+                if old.section_type() == SectionType::Code {
+                    diffs.extend(diff_function_bodies(&old.data, &new.data));
+                }
+            }
+        }
+    }
+
+    for (key, new) in &after_sections {
+        if !before_sections.iter().any(|(k, _)| k == key) {
+            diffs.push(SectionDiff::Added {
+                section: section_label(new),
+                size: new.data.len(),
+            });
+        }
+    }
+
+    diffs
+}
+
+/// Section ID, custom section name and occurrence number
+type SectionKey = (u8, Option<String>, usize);
+
+/// Identify each section by ID, custom name and occurrence so repeated
+/// custom sections are matched in order.
+fn keyed_sections(module: &WasmModule) -> Vec<(SectionKey, &Section)> {
+    let mut occurrences: HashMap<(u8, Option<String>), usize> = HashMap::new();
+
+    module
+        .sections
+        .iter()
+        .map(|section| {
+            let id = (section.id, section.custom_name().map(str::to_string));
+            let occurrence = occurrences.entry(id.clone()).or_insert(0);
+            let key = (id.0, id.1, *occurrence);
+            *occurrence += 1;
+            (key, section)
+        })
+        .collect()
+}
+
+fn section_label(section: &Section) -> String {
+    let label = match section.custom_name() {
+        Some(name) => format!("custom \"{}\"", name),
+        None => format!("{:?}", section.section_type()),
+    };
+
+    match section.offset {
+        Some(offset) => format!("{} @0x{:x}", label, offset),
+        None => label,
+    }
+}
+
+fn diff_function_bodies(before: &[u8], after: &[u8]) -> Vec<SectionDiff> {
+    let before = function_bodies(before).unwrap_or_default();
+    let after = function_bodies(after).unwrap_or_default();
+    let mut diffs = Vec::new();
+
+    for index in 0..before.len().max(after.len()) {
+        let old = before.get(index).copied().unwrap_or_default();
+        let new = after.get(index).copied().unwrap_or_default();
+        if old != new {
+            diffs.push(SectionDiff::FunctionBody {
+                index,
+                before: old.len(),
+                after: new.len(),
+            });
+        }
+    }
+
+    diffs
+}
+
+/// Split a Code section payload into its function bodies
+fn function_bodies(data: &[u8]) -> Option<Vec<&[u8]>> {
+    let mut decoder = LebDecoder::new(data);
+    let count = decoder.read_u32().ok()?;
+    let mut bodies = Vec::new();
+
+    for _ in 0..count {
+        let size = decoder.read_u32().ok()? as usize;
+        bodies.push(decoder.read_bytes(size).ok()?);
+    }
+
+    Some(bodies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wasm::WasmParser;
+
+    fn sample_module() -> WasmModule {
+        WasmParser::parse_module(&[
+            0x00, 0x61, 0x73, 0x6D, // WASM magic number
+            0x01, 0x00, 0x00, 0x00, // Version
+            // Type section
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // Function section
+            0x03, 0x02, 0x01, 0x00, // Code section
+            0x0A, 0x04, 0x01, 0x02, 0x00, 0x0B,
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diff_identical_modules() {
+        let module = sample_module();
+        assert!(diff(&module, &module.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_grown_code_section() {
+        let before = sample_module();
+        let mut after = before.clone();
+
+        // Insert dead code (two `nop`s) into the only function body
+        let code = after
+            .sections
+            .iter_mut()
+            .find(|s| s.section_type() == SectionType::Code)
+            .unwrap();
+        code.data = vec![0x01, 0x04, 0x00, 0x01, 0x01, 0x0B];
+
+        let diffs = diff(&before, &after);
+        assert!(diffs.contains(&SectionDiff::Resized {
+            section: "Code @0x12".to_string(),
+            before: 4,
+            after: 6,
+        }));
+        assert!(diffs.contains(&SectionDiff::FunctionBody {
+            index: 0,
+            before: 2,
+            after: 4,
+        }));
+    }
+
+    #[test]
+    fn test_diff_added_and_removed_sections() {
+        let before = sample_module();
+        let mut after = before.clone();
+        after
+            .sections
+            .retain(|s| s.section_type() != SectionType::Type);
+        after.sections.push(Section::custom("producers", &[0x00]));
+
+        let diffs = diff(&before, &after);
+        assert_eq!(diffs.len(), 2);
+        assert!(
+            matches!(&diffs[0], SectionDiff::Removed { section, .. } if section == "Type @0x8")
+        );
+        assert_eq!(
+            diffs[1].to_string(),
+            "+ custom \"producers\" section (11 bytes)"
+        );
+    }
+}
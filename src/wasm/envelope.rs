@@ -0,0 +1,63 @@
+use crate::error::Result;
+use crate::wasm::module::{Section, WasmModule, RESERVED_SECTION_PREFIX};
+use crate::wasm::{WasmParser, WasmWriter};
+
+/// Name of the custom section that carries the encrypted payload
+pub const PAYLOAD_SECTION: &str = ".rwc.payload";
+
+/// Wrap an encrypted payload in a minimal valid WASM module.
+///
+/// The module holds a `.rwc.payload` custom section, plus a readable
+/// `.rwc.notice` section if `notice` is given, so it passes
+/// `WebAssembly.validate` while the payload stays opaque.
+pub fn wrap_payload(payload: &[u8], notice: Option<&str>) -> Result<Vec<u8>> {
+    debug_assert!(PAYLOAD_SECTION.starts_with(RESERVED_SECTION_PREFIX));
+
+    let mut module = WasmModule {
+        version: 1,
+        sections: vec![Section::custom(PAYLOAD_SECTION, payload)],
+    };
+    if let Some(notice) = notice {
+        module.set_notice(notice);
+    }
+    WasmWriter::serialize_module(&module)
+}
+
+/// Extract the payload from an envelope, or `None` if the data is not one
+pub fn unwrap_payload(data: &[u8]) -> Result<Option<Vec<u8>>> {
+    if !data.starts_with(b"\0asm") {
+        return Ok(None);
+    }
+
+    let module = WasmParser::parse_module(data)?;
+    Ok(module
+        .custom_section(PAYLOAD_SECTION)
+        .and_then(|section| section.custom_content())
+        .map(<[u8]>::to_vec))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope_round_trip() {
+        let payload = vec![0xA5u8; 300];
+        let envelope = wrap_payload(&payload, Some("(c) Example Corp")).unwrap();
+
+        assert!(WasmParser::validate_wasm(&envelope).is_ok());
+        assert_eq!(unwrap_payload(&envelope).unwrap(), Some(payload));
+
+        let module = WasmParser::parse_module(&envelope).unwrap();
+        let notice = module.custom_section(crate::wasm::NOTICE_SECTION).unwrap();
+        assert_eq!(notice.custom_content(), Some(&b"(c) Example Corp"[..]));
+    }
+
+    #[test]
+    fn test_unwrap_non_envelope() {
+        assert_eq!(unwrap_payload(b"nonce and ciphertext").unwrap(), None);
+
+        let plain_module = [0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00];
+        assert_eq!(unwrap_payload(&plain_module).unwrap(), None);
+    }
+}
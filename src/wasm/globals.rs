@@ -0,0 +1,188 @@
+use log::{info, warn};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use sha2::{Digest, Sha256};
+use wasmparser::{BinaryReader, GlobalSectionReader, Operator};
+
+use crate::error::Result;
+use crate::wasm::leb;
+use crate::wasm::module::{Section, SectionType, WasmModule};
+
+const I32_CONST: u8 = 0x41;
+const I64_CONST: u8 = 0x42;
+const I32_SUB: u8 = 0x6B;
+const I64_SUB: u8 = 0x7D;
+const END: u8 = 0x0B;
+
+/// Rewrite constant `i32`/`i64` global initializers as `(const a) (const b) (sub)`.
+///
+/// `b` is pseudo-random and `a - b` wraps back to the original value, so the
+/// constant no longer appears literally. The masks come from `seed`, or from a
+/// hash of the module when `None`, so the same input always gives the same
+/// output. The rewritten expressions need the extended-const proposal. Other
+/// initializers (`global.get`, floats, refs) are copied verbatim. Returns how
+/// many initializers were rewritten.
+pub fn obfuscate_globals(module: &mut WasmModule, seed: Option<u64>) -> Result<usize> {
+    let seed = seed.unwrap_or_else(|| content_seed(module));
+    let section = match module
+        .sections
+        .iter_mut()
+        .find(|s| s.section_type() == SectionType::Global)
+    {
+        Some(section) => section,
+        None => {
+            info!("No global section found, skipping global obfuscation");
+            return Ok(0);
+        }
+    };
+
+    let reader = GlobalSectionReader::new(BinaryReader::new(&section.data, 0))?;
+    let mut output = Vec::with_capacity(section.data.len() * 2);
+    leb::write_u32(&mut output, reader.count());
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut rewritten = 0;
+    for entry in reader.into_iter_with_offsets() {
+        let (offset, global) = entry?;
+        let expr = global.init_expr.get_binary_reader();
+        let expr_start = expr.original_position();
+
+        // Value type and mutability are kept as-is
+        output.extend_from_slice(&section.data[offset..expr_start]);
+
+        let operators = global
+            .init_expr
+            .get_operators_reader()
+            .into_iter()
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        match operators.as_slice() {
+            [Operator::I32Const { value }, Operator::End] => {
+                let mask: i32 = rng.random();
+                output.push(I32_CONST);
+                leb::write_i32(&mut output, value.wrapping_add(mask));
+                output.push(I32_CONST);
+                leb::write_i32(&mut output, mask);
+                output.extend_from_slice(&[I32_SUB, END]);
+                rewritten += 1;
+            }
+            [Operator::I64Const { value }, Operator::End] => {
+                let mask: i64 = rng.random();
+                output.push(I64_CONST);
+                leb::write_i64(&mut output, value.wrapping_add(mask));
+                output.push(I64_CONST);
+                leb::write_i64(&mut output, mask);
+                output.extend_from_slice(&[I64_SUB, END]);
+                rewritten += 1;
+            }
+            _ => output
+                .extend_from_slice(&section.data[expr_start..expr_start + expr.bytes_remaining()]),
+        }
+    }
+
+    if rewritten > 0 {
+        *section = Section::new(section.id, output);
+        warn!(
+            "Obfuscated {} global initializers; the module now needs an engine with extended-const support",
+            rewritten
+        );
+    }
+    Ok(rewritten)
+}
+
+/// Seed derived from every section, for reproducible output without a caller seed
+fn content_seed(module: &WasmModule) -> u64 {
+    let mut hasher = Sha256::new();
+    for section in &module.sections {
+        hasher.update([section.id]);
+        hasher.update((section.data.len() as u64).to_le_bytes());
+        hasher.update(&section.data);
+    }
+    u64::from_le_bytes(hasher.finalize()[..8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wasm::{WasmParser, WasmWriter};
+    use wasmparser::ConstExpr;
+
+    /// Evaluate an integer constant expression as the engine would
+    fn eval(expr: &ConstExpr) -> i64 {
+        let mut stack = Vec::new();
+        for operator in expr.get_operators_reader() {
+            match operator.unwrap() {
+                Operator::I32Const { value } => stack.push(value as i64),
+                Operator::I64Const { value } => stack.push(value),
+                Operator::I32Sub => {
+                    let (b, a) = (stack.pop().unwrap(), stack.pop().unwrap());
+                    stack.push((a as i32).wrapping_sub(b as i32) as i64);
+                }
+                Operator::I64Sub => {
+                    let (b, a) = (stack.pop().unwrap(), stack.pop().unwrap());
+                    stack.push(a.wrapping_sub(b));
+                }
+                Operator::End => break,
+                other => panic!("unexpected operator {:?}", other),
+            }
+        }
+        stack.pop().unwrap()
+    }
+
+    fn global_values(wasm: &[u8]) -> Vec<i64> {
+        let module = WasmParser::parse_module(wasm).unwrap();
+        let section = module.section(SectionType::Global).unwrap();
+        GlobalSectionReader::new(BinaryReader::new(&section.data, 0))
+            .unwrap()
+            .into_iter()
+            .map(|g| eval(&g.unwrap().init_expr))
+            .collect()
+    }
+
+    #[test]
+    fn test_obfuscate_globals_preserves_values() {
+        let wasm_data = wat::parse_str(
+            r#"(module
+                (global $sp (mut i32) (i32.const 42))
+                (global $min i32 (i32.const -2147483648))
+                (global $flags i64 (i64.const -7)))"#,
+        )
+        .unwrap();
+
+        let mut module = WasmParser::parse_module(&wasm_data).unwrap();
+        assert_eq!(obfuscate_globals(&mut module, None).unwrap(), 3);
+        let output = WasmWriter::serialize_module(&module).unwrap();
+
+        wasmparser::Validator::new().validate_all(&output).unwrap();
+        assert_eq!(global_values(&output), vec![42, i32::MIN as i64, -7]);
+    }
+
+    #[test]
+    fn test_obfuscate_globals_keeps_other_initializers() {
+        let wasm_data = wat::parse_str(
+            r#"(module
+                (import "env" "base" (global $base i32))
+                (global $copy i32 (global.get $base))
+                (global $pi f32 (f32.const 3.14)))"#,
+        )
+        .unwrap();
+
+        let mut module = WasmParser::parse_module(&wasm_data).unwrap();
+        assert_eq!(obfuscate_globals(&mut module, None).unwrap(), 0);
+        assert_eq!(WasmWriter::serialize_module(&module).unwrap(), wasm_data);
+    }
+
+    #[test]
+    fn test_obfuscate_globals_is_reproducible() {
+        let wasm_data = wat::parse_str("(module (global i32 (i32.const 7)))").unwrap();
+        let run = |seed| {
+            let mut module = WasmParser::parse_module(&wasm_data).unwrap();
+            obfuscate_globals(&mut module, seed).unwrap();
+            WasmWriter::serialize_module(&module).unwrap()
+        };
+
+        assert_eq!(run(None), run(None));
+        assert_eq!(run(Some(1)), run(Some(1)));
+        assert_ne!(run(Some(1)), run(Some(2)));
+        assert_eq!(global_values(&run(Some(2))), vec![7]);
+    }
+}
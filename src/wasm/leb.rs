@@ -0,0 +1,326 @@
+use crate::error::{Result, RusWaCipherError};
+
+/// Reads LEB128 values and raw bytes from a slice, tracking the position.
+///
+/// Every read fails on truncated input and on values that overflow the target
+/// type, including over-long encodings with non-zero (or non-sign) padding bits.
+#[derive(Debug, Clone)]
+pub struct LebDecoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> LebDecoder<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self::at(data, 0)
+    }
+
+    /// Start decoding at byte offset `pos`
+    pub fn at(data: &'a [u8], pos: usize) -> Self {
+        LebDecoder { data, pos }
+    }
+
+    /// Current byte offset into the slice
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Bytes not yet consumed
+    pub fn remaining(&self) -> &'a [u8] {
+        self.data.get(self.pos..).unwrap_or(&[])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining().is_empty()
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32> {
+        self.read_unsigned(32).map(|value| value as u32)
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64> {
+        self.read_unsigned(64)
+    }
+
+    pub fn read_i32(&mut self) -> Result<i32> {
+        self.read_signed(32).map(|value| value as i32)
+    }
+
+    pub fn read_i64(&mut self) -> Result<i64> {
+        self.read_signed(64)
+    }
+
+    /// Read `len` raw bytes
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len());
+        let end = end.ok_or_else(|| {
+            RusWaCipherError::InvalidInput(format!(
+                "Unexpected end of data: {} bytes needed at offset {}",
+                len, self.pos
+            ))
+        })?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn read_byte(&mut self) -> Result<u8> {
+        let byte = *self.data.get(self.pos).ok_or_else(|| {
+            RusWaCipherError::InvalidInput("Unexpected end of data in LEB128 value".to_string())
+        })?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn read_unsigned(&mut self, bits: u32) -> Result<u64> {
+        let mut result: u64 = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.read_byte()?;
+
+            // The final byte may only use the bits left in the target type
+            let available = bits - shift;
+            if available < 7 && (byte & 0x80 != 0 || (byte & 0x7F) >> available != 0) {
+                return Err(overflow(bits, false));
+            }
+            result |= ((byte & 0x7F) as u64) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+            shift += 7;
+        }
+    }
+
+    fn read_signed(&mut self, bits: u32) -> Result<i64> {
+        let mut result: i64 = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.read_byte()?;
+
+            // In the final byte, the bits above the sign bit must all copy it
+            let available = bits - shift;
+            if available < 7 {
+                let padding = (byte & 0x7F) >> (available - 1);
+                if byte & 0x80 != 0 || (padding != 0 && padding != 0x7F >> (available - 1)) {
+                    return Err(overflow(bits, true));
+                }
+            }
+            result |= ((byte & 0x7F) as i64) << shift;
+            shift += 7;
+
+            if byte & 0x80 == 0 {
+                if shift < 64 && byte & 0x40 != 0 {
+                    result |= -1i64 << shift;
+                }
+                return Ok(result);
+            }
+        }
+    }
+}
+
+fn overflow(bits: u32, signed: bool) -> RusWaCipherError {
+    RusWaCipherError::InvalidInput(format!(
+        "LEB128 value overflows {}{}",
+        if signed { "i" } else { "u" },
+        bits
+    ))
+}
+
+/// Read an unsigned 32-bit LEB128 value, advancing `pos`
+pub fn read_u32(data: &[u8], pos: &mut usize) -> Result<u32> {
+    let mut decoder = LebDecoder::at(data, *pos);
+    let value = decoder.read_u32()?;
+    *pos = decoder.position();
+    Ok(value)
+}
+
+/// Append an unsigned 32-bit LEB128 value to `output`
+pub fn write_u32(output: &mut Vec<u8>, value: u32) {
+    write_u64(output, value as u64);
+}
+
+/// Append an unsigned 64-bit LEB128 value to `output`
+pub fn write_u64(output: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            output.push(byte);
+            return;
+        }
+        output.push(byte | 0x80);
+    }
+}
+
+/// Append a signed 32-bit LEB128 value to `output`
+pub fn write_i32(output: &mut Vec<u8>, value: i32) {
+    write_i64(output, value as i64);
+}
+
+/// Append a signed 64-bit LEB128 value to `output`
+pub fn write_i64(output: &mut Vec<u8>, mut value: i64) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        // Done once the remaining bits are all copies of the sign bit just written
+        if (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0) {
+            output.push(byte);
+            return;
+        }
+        output.push(byte | 0x80);
+    }
+}
+
+/// Convert a length to the u32 the WASM binary format requires,
+/// erroring instead of silently truncating
+pub fn checked_length(length: usize, what: &str) -> Result<u32> {
+    u32::try_from(length).map_err(|_| {
+        RusWaCipherError::InvalidInput(format!(
+            "{} is {} bytes, exceeding the WASM limit of {} bytes",
+            what,
+            length,
+            u32::MAX
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_u32() {
+        let mut pos = 0;
+        assert_eq!(read_u32(&[0xE5, 0x8E, 0x26], &mut pos).unwrap(), 624485);
+        assert_eq!(pos, 3);
+
+        let mut pos = 0;
+        assert!(read_u32(&[0x80, 0x80], &mut pos).is_err());
+
+        let mut pos = 0;
+        assert!(read_u32(&[0xFF, 0xFF, 0xFF, 0xFF, 0x7F], &mut pos).is_err());
+    }
+
+    #[test]
+    fn test_write_signed_round_trip() {
+        for value in [0, 1, -1, 63, 64, -64, -65, i32::MIN, i32::MAX] {
+            let mut output = Vec::new();
+            write_i32(&mut output, value);
+            assert_eq!(LebDecoder::new(&output).read_i32().unwrap(), value);
+        }
+        for value in [i64::MIN, i64::MAX, -7, 1 << 40] {
+            let mut output = Vec::new();
+            write_i64(&mut output, value);
+            assert_eq!(LebDecoder::new(&output).read_i64().unwrap(), value);
+        }
+
+        let mut output = Vec::new();
+        write_i32(&mut output, -123456);
+        assert_eq!(output, vec![0xC0, 0xBB, 0x78]);
+    }
+
+    #[test]
+    fn test_write_u32_and_u64() {
+        let mut output = Vec::new();
+        write_u32(&mut output, 624485);
+        assert_eq!(output, vec![0xE5, 0x8E, 0x26]);
+
+        let mut output = Vec::new();
+        write_u64(&mut output, u64::MAX);
+        assert_eq!(output.len(), 10);
+        assert_eq!(output[9], 0x01);
+
+        let mut output = Vec::new();
+        write_u32(&mut output, u32::MAX);
+        let mut pos = 0;
+        assert_eq!(read_u32(&output, &mut pos).unwrap(), u32::MAX);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn test_checked_length_overflow() {
+        assert_eq!(checked_length(1024, "Section").unwrap(), 1024);
+
+        let err = checked_length(u32::MAX as usize + 1, "Section 10").unwrap_err();
+        assert!(err.to_string().contains("Section 10 is 4294967296 bytes"));
+        assert!(err.to_string().contains("exceeding the WASM limit"));
+    }
+
+    #[test]
+    fn test_decoder_unsigned_boundaries() {
+        let mut encoded = Vec::new();
+        for value in [0, 127, 128, u32::MAX as u64] {
+            write_u64(&mut encoded, value);
+        }
+        let mut decoder = LebDecoder::new(&encoded);
+        assert_eq!(decoder.read_u32().unwrap(), 0);
+        assert_eq!(decoder.read_u32().unwrap(), 127);
+        assert_eq!(decoder.read_u32().unwrap(), 128);
+        assert_eq!(decoder.read_u32().unwrap(), u32::MAX);
+        assert!(decoder.is_empty());
+
+        let mut encoded = Vec::new();
+        write_u64(&mut encoded, u64::MAX);
+        assert_eq!(LebDecoder::new(&encoded).read_u64().unwrap(), u64::MAX);
+        assert!(LebDecoder::new(&encoded).read_u32().is_err());
+
+        // u32::MAX + 1 and an 11-byte u64
+        assert!(LebDecoder::new(&[0x80, 0x80, 0x80, 0x80, 0x10])
+            .read_u32()
+            .is_err());
+        let mut too_long = vec![0x80; 10];
+        too_long.push(0x00);
+        assert!(LebDecoder::new(&too_long).read_u64().is_err());
+        assert!(LebDecoder::new(&[0xFF; 9]).read_u64().is_err());
+    }
+
+    #[test]
+    fn test_decoder_signed_boundaries() {
+        assert_eq!(LebDecoder::new(&[0x7F]).read_i32().unwrap(), -1);
+        assert_eq!(LebDecoder::new(&[0x3F]).read_i32().unwrap(), 63);
+        assert_eq!(LebDecoder::new(&[0xC0, 0x00]).read_i32().unwrap(), 64);
+        assert_eq!(LebDecoder::new(&[0x80, 0x7F]).read_i32().unwrap(), -128);
+
+        let i32_min = [0x80, 0x80, 0x80, 0x80, 0x78];
+        let i32_max = [0xFF, 0xFF, 0xFF, 0xFF, 0x07];
+        assert_eq!(LebDecoder::new(&i32_min).read_i32().unwrap(), i32::MIN);
+        assert_eq!(LebDecoder::new(&i32_max).read_i32().unwrap(), i32::MAX);
+        assert!(LebDecoder::new(&[0xFF, 0xFF, 0xFF, 0xFF, 0x0F])
+            .read_i32()
+            .is_err());
+        assert!(LebDecoder::new(&[0x80, 0x80, 0x80, 0x80, 0x70])
+            .read_i32()
+            .is_err());
+
+        let mut i64_min = vec![0x80; 9];
+        i64_min.push(0x7F);
+        let mut i64_max = vec![0xFF; 9];
+        i64_max.push(0x00);
+        assert_eq!(LebDecoder::new(&i64_min).read_i64().unwrap(), i64::MIN);
+        assert_eq!(LebDecoder::new(&i64_max).read_i64().unwrap(), i64::MAX);
+        i64_max[9] = 0x01;
+        assert!(LebDecoder::new(&i64_max).read_i64().is_err());
+    }
+
+    #[test]
+    fn test_decoder_truncated_input() {
+        let truncated = [0x80, 0x80];
+        assert!(LebDecoder::new(&truncated).read_u32().is_err());
+        assert!(LebDecoder::new(&truncated).read_u64().is_err());
+        assert!(LebDecoder::new(&truncated).read_i32().is_err());
+        assert!(LebDecoder::new(&truncated).read_i64().is_err());
+        assert!(LebDecoder::new(&[]).read_u32().is_err());
+
+        let mut decoder = LebDecoder::new(&[0x02, 0xAA, 0xBB]);
+        assert_eq!(decoder.read_u32().unwrap(), 2);
+        assert_eq!(decoder.read_bytes(2).unwrap(), &[0xAA, 0xBB]);
+        assert!(decoder.read_bytes(1).is_err());
+        assert_eq!(decoder.position(), 3);
+    }
+}
@@ -1,5 +1,15 @@
+pub mod diff;
+pub mod envelope;
+pub mod globals;
+pub mod leb;
+pub mod module;
+pub mod names;
 pub mod parser;
+pub mod transform;
 pub mod writer;
 
+pub use diff::{diff, SectionDiff};
+pub use module::{Section, SectionType, WasmModule, NOTICE_SECTION, RESERVED_SECTION_PREFIX};
 pub use parser::WasmParser;
+pub use transform::{run_pass, run_transforms, ObfuscationContext, Transform};
 pub use writer::WasmWriter;
@@ -0,0 +1,355 @@
+use wasmparser::{
+    BinaryReader, CodeSectionReader, FunctionSectionReader, ImportSectionReader, Operator, TypeRef,
+};
+
+use crate::error::{Result, RusWaCipherError};
+use crate::wasm::leb;
+
+/// Name prefix reserved for custom sections added by RusWaCipher
+pub const RESERVED_SECTION_PREFIX: &str = ".rwc.";
+
+/// Custom section holding a license/copyright notice attached with `--notice`
+pub const NOTICE_SECTION: &str = ".rwc.notice";
+
+/// Section IDs defined by the core WASM specification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectionType {
+    Custom,
+    Type,
+    Import,
+    Function,
+    Table,
+    Memory,
+    Global,
+    Export,
+    Start,
+    Element,
+    Code,
+    Data,
+    DataCount,
+    Tag,
+    /// An ID this crate does not know (e.g. a future proposal); kept verbatim
+    Unknown(u8),
+}
+
+impl From<u8> for SectionType {
+    fn from(id: u8) -> Self {
+        match id {
+            0 => SectionType::Custom,
+            1 => SectionType::Type,
+            2 => SectionType::Import,
+            3 => SectionType::Function,
+            4 => SectionType::Table,
+            5 => SectionType::Memory,
+            6 => SectionType::Global,
+            7 => SectionType::Export,
+            8 => SectionType::Start,
+            9 => SectionType::Element,
+            10 => SectionType::Code,
+            11 => SectionType::Data,
+            12 => SectionType::DataCount,
+            13 => SectionType::Tag,
+            other => SectionType::Unknown(other),
+        }
+    }
+}
+
+/// A single section of a WASM module, kept as raw bytes
+#[derive(Debug, Clone)]
+pub struct Section {
+    /// Raw section ID as it appears in the binary
+    pub id: u8,
+    /// Section payload (for custom sections this includes the name)
+    pub data: Vec<u8>,
+    /// Byte offset of the section ID in the module it was parsed from
+    pub offset: Option<usize>,
+}
+
+/// Sections compare by content; where they were parsed from does not matter
+impl PartialEq for Section {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.data == other.data
+    }
+}
+
+impl Eq for Section {}
+
+impl Section {
+    pub fn new(id: u8, data: Vec<u8>) -> Self {
+        Section {
+            id,
+            data,
+            offset: None,
+        }
+    }
+
+    /// Record where the section starts in the original module
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Create a custom section with the given name and content
+    pub fn custom(name: &str, content: &[u8]) -> Self {
+        let mut data = Vec::with_capacity(name.len() + content.len() + 5);
+        leb::write_u32(&mut data, name.len() as u32);
+        data.extend_from_slice(name.as_bytes());
+        data.extend_from_slice(content);
+        Section::new(0, data)
+    }
+
+    pub fn section_type(&self) -> SectionType {
+        SectionType::from(self.id)
+    }
+
+    /// Get the name of a custom section, if this is one
+    pub fn custom_name(&self) -> Option<&str> {
+        self.split_custom().map(|(name, _)| name)
+    }
+
+    /// Get the content of a custom section following its name
+    pub fn custom_content(&self) -> Option<&[u8]> {
+        self.split_custom().map(|(_, content)| content)
+    }
+
+    fn split_custom(&self) -> Option<(&str, &[u8])> {
+        if self.section_type() != SectionType::Custom {
+            return None;
+        }
+
+        let mut decoder = leb::LebDecoder::new(&self.data);
+        let len = decoder.read_u32().ok()? as usize;
+        let name = std::str::from_utf8(decoder.read_bytes(len).ok()?).ok()?;
+        Some((name, decoder.remaining()))
+    }
+}
+
+/// A WASM module split into its sections
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmModule {
+    pub version: u32,
+    pub sections: Vec<Section>,
+}
+
+impl WasmModule {
+    /// Find the first section of the given type
+    pub fn section(&self, section_type: SectionType) -> Option<&Section> {
+        self.sections
+            .iter()
+            .find(|s| s.section_type() == section_type)
+    }
+
+    /// Find the first custom section with the given name
+    pub fn custom_section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|s| s.custom_name() == Some(name))
+    }
+
+    /// Find the first custom section with the given name for modification
+    pub fn custom_section_mut(&mut self, name: &str) -> Option<&mut Section> {
+        self.sections
+            .iter_mut()
+            .find(|s| s.custom_name() == Some(name))
+    }
+
+    /// Attach `text` as the `.rwc.notice` custom section, replacing any existing notice
+    pub fn set_notice(&mut self, text: &str) {
+        self.sections
+            .retain(|s| s.custom_name() != Some(NOTICE_SECTION));
+        self.sections
+            .push(Section::custom(NOTICE_SECTION, text.as_bytes()));
+    }
+
+    /// Remove all custom sections whose name starts with `prefix`, returning how many were removed
+    pub fn strip_custom_sections(&mut self, prefix: &str) -> usize {
+        let before = self.sections.len();
+        self.sections
+            .retain(|s| !s.custom_name().is_some_and(|name| name.starts_with(prefix)));
+        before - self.sections.len()
+    }
+
+    /// Number of functions in the index space (imported plus defined)
+    pub fn function_count(&self) -> Result<u32> {
+        let (imported, defined) = self.function_counts()?;
+        Ok(imported + defined)
+    }
+
+    fn function_counts(&self) -> Result<(u32, u32)> {
+        let mut imported = 0;
+        if let Some(section) = self.section(SectionType::Import) {
+            for import in ImportSectionReader::new(BinaryReader::new(&section.data, 0))? {
+                if matches!(import?.ty, TypeRef::Func(_)) {
+                    imported += 1;
+                }
+            }
+        }
+        let defined = match self.section(SectionType::Function) {
+            Some(section) => {
+                FunctionSectionReader::new(BinaryReader::new(&section.data, 0))?.count()
+            }
+            None => 0,
+        };
+        Ok((imported, defined))
+    }
+
+    /// Check that every `call` and `return_call` targets an existing function.
+    ///
+    /// Passes that rewrite function indices can otherwise produce a module
+    /// that only fails later, at instantiation, with an engine-specific error.
+    pub fn validate_indices(&self) -> Result<()> {
+        let Some(code) = self.section(SectionType::Code) else {
+            return Ok(());
+        };
+        let (imported, defined) = self.function_counts()?;
+        let count = imported + defined;
+
+        for (i, body) in CodeSectionReader::new(BinaryReader::new(&code.data, 0))?
+            .into_iter()
+            .enumerate()
+        {
+            let mut operators = body?.get_operators_reader()?;
+            while !operators.eof() {
+                let target = match operators.read()? {
+                    Operator::Call { function_index } | Operator::ReturnCall { function_index } => {
+                        function_index
+                    }
+                    _ => continue,
+                };
+                if target >= count {
+                    return Err(RusWaCipherError::InvalidInput(format!(
+                        "Function {} calls function {}, but the module only has {} functions",
+                        imported + i as u32,
+                        target,
+                        count
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Render the module as WAT text, for debugging obfuscation passes
+    #[cfg(feature = "wat-dump")]
+    pub fn to_wat(&self) -> Result<String> {
+        let bytes = crate::wasm::WasmWriter::serialize_module(self)?;
+        wasmprinter::print_bytes(bytes).map_err(|e| {
+            crate::error::RusWaCipherError::InvalidInput(format!("Failed to print WAT: {}", e))
+        })
+    }
+
+    /// Render the module as WAT text (requires the `wat-dump` feature)
+    #[cfg(not(feature = "wat-dump"))]
+    pub fn to_wat(&self) -> Result<String> {
+        Err(crate::error::RusWaCipherError::Config(
+            "WAT dumping is not compiled in; rebuild with --features wat-dump".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_section_type_from_id() {
+        assert_eq!(SectionType::from(0), SectionType::Custom);
+        assert_eq!(SectionType::from(10), SectionType::Code);
+        assert_eq!(SectionType::from(42), SectionType::Unknown(42));
+    }
+
+    #[test]
+    fn test_custom_section_name_and_content() {
+        let section = Section::custom("name", &[1, 2, 3]);
+        assert_eq!(section.section_type(), SectionType::Custom);
+        assert_eq!(section.custom_name(), Some("name"));
+        assert_eq!(section.custom_content(), Some(&[1u8, 2, 3][..]));
+
+        let code = Section::new(10, vec![0x00]);
+        assert_eq!(code.custom_name(), None);
+    }
+
+    #[test]
+    fn test_section_equality_ignores_offset() {
+        let parsed = Section::new(10, vec![0x00]).with_offset(8);
+        assert_eq!(parsed, Section::new(10, vec![0x00]));
+        assert_ne!(parsed, Section::new(10, vec![0x01]).with_offset(8));
+    }
+
+    #[test]
+    fn test_strip_custom_sections() {
+        let mut module = WasmModule {
+            version: 1,
+            sections: vec![
+                Section::custom(".rwc.vm", &[1]),
+                Section::new(1, vec![0x00]),
+                Section::custom("name", &[]),
+                Section::custom(".rwc.meta", &[]),
+            ],
+        };
+
+        assert_eq!(module.strip_custom_sections(RESERVED_SECTION_PREFIX), 2);
+        assert_eq!(module.sections.len(), 2);
+        assert!(module.custom_section("name").is_some());
+    }
+
+    #[cfg(feature = "wat-dump")]
+    #[test]
+    fn test_to_wat() {
+        let wasm_data =
+            wat::parse_str(r#"(module (func (export "answer") (result i32) i32.const 42))"#)
+                .unwrap();
+        let module = crate::wasm::WasmParser::parse_module(&wasm_data).unwrap();
+
+        let text = module.to_wat().unwrap();
+        assert!(text.contains("(module"));
+        assert!(text.contains("i32.const 42"));
+    }
+
+    #[test]
+    fn test_set_notice_replaces_existing() {
+        let mut module = WasmModule {
+            version: 1,
+            sections: vec![Section::new(1, vec![0x00])],
+        };
+        module.set_notice("(c) first");
+        module.set_notice("(c) second");
+
+        let notices: Vec<_> = module
+            .sections
+            .iter()
+            .filter(|s| s.custom_name() == Some(NOTICE_SECTION))
+            .collect();
+        assert_eq!(notices.len(), 1);
+        assert_eq!(notices[0].custom_content(), Some(&b"(c) second"[..]));
+    }
+
+    #[test]
+    fn test_validate_indices_rejects_out_of_range_call() {
+        let wasm_data = wat::parse_str(
+            r#"(module
+                (import "env" "log" (func $log))
+                (func $a call $log)
+                (func $b call $a))"#,
+        )
+        .unwrap();
+        let mut module = crate::wasm::WasmParser::parse_module(&wasm_data).unwrap();
+        assert_eq!(module.function_count().unwrap(), 3);
+        module.validate_indices().unwrap();
+
+        // Retarget `call $a` in $b (opcode 0x10, index 1) to a function that does not exist
+        let code = module
+            .sections
+            .iter_mut()
+            .find(|s| s.section_type() == SectionType::Code)
+            .unwrap();
+        let pos = code
+            .data
+            .windows(2)
+            .rposition(|w| w == [0x10, 0x01])
+            .unwrap();
+        code.data[pos + 1] = 0x07;
+
+        let err = module.validate_indices().unwrap_err().to_string();
+        assert!(err.contains("Function 2 calls function 7"));
+        assert!(err.contains("only has 3 functions"));
+    }
+}
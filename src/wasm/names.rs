@@ -0,0 +1,305 @@
+use std::collections::HashSet;
+
+use log::{debug, info};
+use wasmparser::{
+    BinaryReader, ExportSectionReader, ExternalKind, IndirectNameMap, Name, NameMap,
+    NameSectionReader,
+};
+
+use crate::error::Result;
+use crate::wasm::leb;
+use crate::wasm::module::{Section, SectionType, WasmModule};
+
+/// Names hosts look up directly; a trailing `*` matches any suffix
+pub const RESERVED_NAMES: &[&str] = &[
+    "_start",
+    "_initialize",
+    "memory",
+    "__indirect_function_table",
+    "__wbindgen_*",
+    "__wbg_*",
+];
+
+/// Whether `name` is in [`RESERVED_NAMES`] or the user-supplied `preserve` list
+pub fn is_preserved(name: &str, preserve: &[String]) -> bool {
+    RESERVED_NAMES
+        .iter()
+        .copied()
+        .chain(preserve.iter().map(String::as_str))
+        .any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == pattern,
+        })
+}
+
+/// Replace all names in the `name` custom section with opaque identifiers.
+///
+/// Function names become `f0`, `f1`, ..., locals `l0`, `l1`, ... and so on,
+/// keyed by their original index so the section still maps every entry a
+/// debugger expects. The module name and unknown subsections are dropped.
+/// Names of items exported under a preserved name (see [`is_preserved`]), and
+/// names that are themselves preserved, are kept as they are.
+/// Returns `false` if the module has no name section.
+pub fn scramble_names(module: &mut WasmModule, preserve: &[String]) -> Result<bool> {
+    let exported = preserved_exports(module, preserve)?;
+    let keep = |subsection: u8, index: u32, name: &str| {
+        exported.contains(&(subsection, index)) || is_preserved(name, preserve)
+    };
+
+    let section = match module.custom_section_mut("name") {
+        Some(section) => section,
+        None => {
+            info!("No name section found, skipping name scrambling");
+            return Ok(false);
+        }
+    };
+
+    let content = section.custom_content().unwrap_or_default();
+    let reader = NameSectionReader::new(BinaryReader::new(content, 0));
+    let mut scrambled = Vec::new();
+
+    for subsection in reader {
+        let (id, payload) = match subsection? {
+            Name::Module { .. } => {
+                debug!("Dropping module name subsection");
+                continue;
+            }
+            Name::Function(map) => (1, scramble_map(map, "f", &|i, n| keep(1, i, n))?),
+            Name::Local(map) => (2, scramble_indirect_map(map, "l")?),
+            Name::Label(map) => (3, scramble_indirect_map(map, "label")?),
+            Name::Type(map) => (4, scramble_map(map, "t", &|_, _| false)?),
+            Name::Table(map) => (5, scramble_map(map, "table", &|i, n| keep(5, i, n))?),
+            Name::Memory(map) => (6, scramble_map(map, "mem", &|i, n| keep(6, i, n))?),
+            Name::Global(map) => (7, scramble_map(map, "g", &|i, n| keep(7, i, n))?),
+            Name::Element(map) => (8, scramble_map(map, "elem", &|_, _| false)?),
+            Name::Data(map) => (9, scramble_map(map, "data", &|_, _| false)?),
+            Name::Field(map) => (10, scramble_indirect_map(map, "field")?),
+            Name::Tag(map) => (11, scramble_map(map, "tag", &|i, n| keep(11, i, n))?),
+            Name::Unknown { ty, .. } => {
+                debug!("Dropping unknown name subsection {}", ty);
+                continue;
+            }
+        };
+
+        scrambled.push(id);
+        leb::write_u32(
+            &mut scrambled,
+            leb::checked_length(payload.len(), "Name subsection")?,
+        );
+        scrambled.extend_from_slice(&payload);
+    }
+
+    *section = Section::custom("name", &scrambled);
+    info!("Scrambled name section");
+
+    Ok(true)
+}
+
+/// `(name subsection id, index)` of every item exported under a preserved name
+fn preserved_exports(module: &WasmModule, preserve: &[String]) -> Result<HashSet<(u8, u32)>> {
+    let mut exported = HashSet::new();
+    let Some(section) = module.section(SectionType::Export) else {
+        return Ok(exported);
+    };
+
+    for export in ExportSectionReader::new(BinaryReader::new(&section.data, 0))? {
+        let export = export?;
+        if !is_preserved(export.name, preserve) {
+            continue;
+        }
+        let subsection = match export.kind {
+            ExternalKind::Func => 1,
+            ExternalKind::Table => 5,
+            ExternalKind::Memory => 6,
+            ExternalKind::Global => 7,
+            ExternalKind::Tag => 11,
+        };
+        exported.insert((subsection, export.index));
+    }
+
+    Ok(exported)
+}
+
+fn scramble_map(map: NameMap, prefix: &str, keep: &dyn Fn(u32, &str) -> bool) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    leb::write_u32(&mut output, map.count());
+
+    for naming in map {
+        let naming = naming?;
+        leb::write_u32(&mut output, naming.index);
+        if keep(naming.index, naming.name) {
+            debug!("Preserving name {}", naming.name);
+            write_name(&mut output, naming.name);
+        } else {
+            write_name(&mut output, &format!("{}{}", prefix, naming.index));
+        }
+    }
+
+    Ok(output)
+}
+
+fn scramble_indirect_map(map: IndirectNameMap, prefix: &str) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    leb::write_u32(&mut output, map.count());
+
+    for indirect in map {
+        let indirect = indirect?;
+        leb::write_u32(&mut output, indirect.index);
+        output.extend_from_slice(&scramble_map(indirect.names, prefix, &|_, _| false)?);
+    }
+
+    Ok(output)
+}
+
+fn write_name(output: &mut Vec<u8>, name: &str) {
+    leb::write_u32(output, name.len() as u32);
+    output.extend_from_slice(name.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wasm::{WasmParser, WasmWriter};
+
+    fn name_map(entries: &[(u32, &str)]) -> Vec<u8> {
+        let mut output = Vec::new();
+        leb::write_u32(&mut output, entries.len() as u32);
+        for (index, name) in entries {
+            leb::write_u32(&mut output, *index);
+            write_name(&mut output, name);
+        }
+        output
+    }
+
+    fn subsection(id: u8, payload: &[u8]) -> Vec<u8> {
+        let mut output = vec![id];
+        leb::write_u32(&mut output, payload.len() as u32);
+        output.extend_from_slice(payload);
+        output
+    }
+
+    fn module_with_name_section() -> Vec<u8> {
+        let mut module_name = Vec::new();
+        write_name(&mut module_name, "password_checker");
+
+        let mut locals = Vec::new();
+        leb::write_u32(&mut locals, 1);
+        leb::write_u32(&mut locals, 0);
+        locals.extend_from_slice(&name_map(&[(0, "secret_input")]));
+
+        let mut content = subsection(0, &module_name);
+        content.extend(subsection(1, &name_map(&[(0, "check_password_strength")])));
+        content.extend(subsection(2, &locals));
+
+        let mut module = WasmParser::parse_module(&[
+            0x00, 0x61, 0x73, 0x6D, // WASM magic number
+            0x01, 0x00, 0x00, 0x00, // Version
+            0x01, 0x05, 0x01, 0x60, 0x01, 0x7F, 0x00, // Type section: (i32) -> ()
+            0x03, 0x02, 0x01, 0x00, // Function section
+            0x0A, 0x04, 0x01, 0x02, 0x00, 0x0B, // Code section
+        ])
+        .unwrap();
+        module.sections.push(Section::custom("name", &content));
+        WasmWriter::serialize_module(&module).unwrap()
+    }
+
+    #[test]
+    fn test_scramble_names_removes_original_names() {
+        let wasm_data = module_with_name_section();
+        assert!(WasmParser::validate_wasm(&wasm_data).is_ok());
+
+        let mut module = WasmParser::parse_module(&wasm_data).unwrap();
+        assert!(scramble_names(&mut module, &[]).unwrap());
+        let output = WasmWriter::serialize_module(&module).unwrap();
+
+        for original in [
+            "password_checker",
+            "check_password_strength",
+            "secret_input",
+        ] {
+            assert!(!output
+                .windows(original.len())
+                .any(|w| w == original.as_bytes()));
+        }
+
+        // The rewritten section must still parse and map the same indices
+        assert!(WasmParser::validate_wasm(&output).is_ok());
+        let module = WasmParser::parse_module(&output).unwrap();
+        let content = module.custom_section("name").unwrap().custom_content();
+        let reader = NameSectionReader::new(BinaryReader::new(content.unwrap(), 0));
+
+        let mut seen_function = false;
+        for subsection in reader {
+            match subsection.unwrap() {
+                Name::Function(map) => {
+                    let naming = map.into_iter().next().unwrap().unwrap();
+                    assert_eq!((naming.index, naming.name), (0, "f0"));
+                    seen_function = true;
+                }
+                Name::Local(map) => {
+                    let locals = map.into_iter().next().unwrap().unwrap();
+                    let naming = locals.names.into_iter().next().unwrap().unwrap();
+                    assert_eq!(naming.name, "l0");
+                }
+                Name::Module { .. } => panic!("module name should be dropped"),
+                _ => {}
+            }
+        }
+        assert!(seen_function);
+    }
+
+    #[test]
+    fn test_scramble_names_without_name_section() {
+        let mut module = WasmParser::parse_module(&[
+            0x00, 0x61, 0x73, 0x6D, // WASM magic number
+            0x01, 0x00, 0x00, 0x00, // Version
+        ])
+        .unwrap();
+
+        assert!(!scramble_names(&mut module, &[]).unwrap());
+        assert!(module.sections.is_empty());
+    }
+
+    #[test]
+    fn test_scramble_names_keeps_preserved_exports() {
+        let wasm_data = wat::parse_str(
+            r#"(module
+                (memory $heap (export "memory") 1)
+                (func $__wbindgen_malloc (export "__wbindgen_malloc") (param i32) (result i32)
+                    local.get 0)
+                (func $internal_api (export "api") (result i32) i32.const 1)
+                (func $check_password (result i32) i32.const 0))"#,
+        )
+        .unwrap();
+
+        let mut module = WasmParser::parse_module(&wasm_data).unwrap();
+        let exports = module.section(SectionType::Export).cloned();
+        assert!(scramble_names(&mut module, &["api".to_string()]).unwrap());
+        let output = WasmWriter::serialize_module(&module).unwrap();
+
+        let contains = |name: &str| output.windows(name.len()).any(|w| w == name.as_bytes());
+        assert!(!contains("check_password"));
+
+        let module = WasmParser::parse_module(&output).unwrap();
+        assert_eq!(module.section(SectionType::Export).cloned(), exports);
+        let content = module.custom_section("name").unwrap().custom_content();
+        let reader = NameSectionReader::new(BinaryReader::new(content.unwrap(), 0));
+
+        for subsection in reader {
+            match subsection.unwrap() {
+                Name::Function(map) => {
+                    let names: Vec<_> = map
+                        .into_iter()
+                        .map(|naming| naming.unwrap().name.to_string())
+                        .collect();
+                    assert_eq!(names, vec!["__wbindgen_malloc", "internal_api", "f2"]);
+                }
+                Name::Memory(map) => {
+                    let naming = map.into_iter().next().unwrap().unwrap();
+                    assert_eq!(naming.name, "heap");
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -1,6 +1,10 @@
+use std::path::Path;
+
 use wasmparser::{Parser, Payload};
 
 use crate::error::{Result, RusWaCipherError};
+use crate::wasm::leb;
+use crate::wasm::module::{Section, WasmModule};
 
 pub struct WasmParser;
 
@@ -14,21 +18,7 @@ impl WasmParser {
             ));
         }
 
-        // Check WASM magic number (0x00 0x61 0x73 0x6D)
-        if &data[0..4] != b"\0asm" {
-            return Err(RusWaCipherError::InvalidInput(
-                "Invalid WASM magic number".to_string(),
-            ));
-        }
-
-        // Check version (should be 1)
-        let version = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
-        if version != 1 {
-            return Err(RusWaCipherError::InvalidInput(format!(
-                "Unsupported WASM version: {}",
-                version
-            )));
-        }
+        Self::check_header(data)?;
 
         // Try to parse with wasmparser for more thorough validation
         let parser = Parser::new(0);
@@ -71,6 +61,115 @@ impl WasmParser {
 
         Ok(info)
     }
+
+    /// Detect post-MVP features the module's code relies on.
+    ///
+    /// Returns `"simd"` if any instruction uses the 0xFD prefix and
+    /// `"threads"` if any uses the atomic 0xFE prefix.
+    pub fn detect_features(data: &[u8]) -> Result<Vec<&'static str>> {
+        let mut simd = false;
+        let mut threads = false;
+
+        for payload in Parser::new(0).parse_all(data) {
+            if let Payload::CodeSectionEntry(body) = payload? {
+                let mut reader = body.get_operators_reader()?;
+                while !reader.eof() {
+                    let (_, offset) = reader.read_with_offset()?;
+                    match data.get(offset) {
+                        Some(0xFD) => simd = true,
+                        Some(0xFE) => threads = true,
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let mut features = Vec::new();
+        if simd {
+            features.push("simd");
+        }
+        if threads {
+            features.push("threads");
+        }
+        Ok(features)
+    }
+
+    /// Assemble WAT text into a binary module.
+    ///
+    /// Errors report the line and column in `path`.
+    pub fn assemble_wat(source: &str, path: &Path) -> Result<Vec<u8>> {
+        wat::parse_str(source).map_err(|mut e| {
+            e.set_path(path);
+            RusWaCipherError::InvalidInput(format!("Failed to assemble WAT: {}", e))
+        })
+    }
+
+    /// Split a WASM module into its raw sections
+    pub fn parse_module(data: &[u8]) -> Result<WasmModule> {
+        if data.len() < 8 {
+            return Err(RusWaCipherError::InvalidInput(
+                "Invalid WASM magic number".to_string(),
+            ));
+        }
+
+        let version = Self::check_header(data)?;
+        let mut sections = Vec::new();
+        let mut pos = 8;
+
+        while pos < data.len() {
+            let offset = pos;
+            let id = data[pos];
+            pos += 1;
+
+            let size = leb::read_u32(data, &mut pos).map_err(|e| {
+                RusWaCipherError::InvalidInput(format!(
+                    "Section {} at offset 0x{:x} has an invalid size: {}",
+                    id, offset, e
+                ))
+            })? as usize;
+            let end = pos.checked_add(size).filter(|&end| end <= data.len());
+            let end = end.ok_or_else(|| {
+                RusWaCipherError::InvalidInput(format!(
+                    "Section {} at offset 0x{:x} exceeds module size ({} bytes declared)",
+                    id, offset, size
+                ))
+            })?;
+
+            sections.push(Section::new(id, data[pos..end].to_vec()).with_offset(offset));
+            pos = end;
+        }
+
+        Ok(WasmModule { version, sections })
+    }
+
+    /// Check the magic number and version of an 8-byte preamble, rejecting
+    /// component-model binaries (layer 1 in the upper half of the version field)
+    fn check_header(data: &[u8]) -> Result<u32> {
+        // Check WASM magic number (0x00 0x61 0x73 0x6D)
+        if &data[0..4] != b"\0asm" {
+            return Err(RusWaCipherError::InvalidInput(
+                "Invalid WASM magic number".to_string(),
+            ));
+        }
+
+        if data[6..8] == [0x01, 0x00] {
+            return Err(RusWaCipherError::InvalidInput(format!(
+                "WASM component model not supported (component version {}); only core modules can be processed",
+                u16::from_le_bytes([data[4], data[5]])
+            )));
+        }
+
+        // Check version (should be 1)
+        let version = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+        if version != 1 {
+            return Err(RusWaCipherError::InvalidInput(format!(
+                "Unsupported WASM version: {}",
+                version
+            )));
+        }
+
+        Ok(version)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -85,6 +184,7 @@ pub struct WasmModuleInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::wasm::WasmWriter;
 
     #[test]
     fn test_validate_invalid_wasm() {
@@ -149,6 +249,45 @@ mod tests {
         assert!(WasmParser::validate_wasm(&invalid_data).is_err());
     }
 
+    #[test]
+    fn test_assemble_wat() {
+        let wasm_data = WasmParser::assemble_wat(
+            "(module (func (export \"answer\") (result i32) i32.const 42))",
+            Path::new("answer.wat"),
+        )
+        .unwrap();
+        assert!(WasmParser::validate_wasm(&wasm_data).is_ok());
+        assert_eq!(
+            WasmParser::get_module_info(&wasm_data)
+                .unwrap()
+                .export_count,
+            1
+        );
+
+        let err = WasmParser::assemble_wat("(module\n  (func i32.bogus))", Path::new("bad.wat"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("bad.wat:2:"), "{}", err);
+    }
+
+    #[test]
+    fn test_component_model_rejected() {
+        let component = vec![
+            0x00, 0x61, 0x73, 0x6D, // WASM magic number
+            0x0D, 0x00, 0x01, 0x00, // Component version 13, layer 1
+        ];
+
+        for result in [
+            WasmParser::validate_wasm(&component).err(),
+            WasmParser::parse_module(&component).err(),
+        ] {
+            assert!(result
+                .unwrap()
+                .to_string()
+                .contains("WASM component model not supported"));
+        }
+    }
+
     #[test]
     fn test_validate_wasm_truncated() {
         let invalid_data = vec![
@@ -211,6 +350,114 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_module_sections() {
+        let wasm_data = vec![
+            0x00, 0x61, 0x73, 0x6D, // WASM magic number
+            0x01, 0x00, 0x00, 0x00, // Version
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // Type section
+            0x03, 0x02, 0x01, 0x00, // Function section
+            0x0A, 0x04, 0x01, 0x02, 0x00, 0x0B, // Code section
+        ];
+
+        let module = WasmParser::parse_module(&wasm_data).unwrap();
+        assert_eq!(module.version, 1);
+        let ids: Vec<u8> = module.sections.iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![1, 3, 10]);
+        assert_eq!(module.sections[2].data, vec![0x01, 0x02, 0x00, 0x0B]);
+    }
+
+    #[test]
+    fn test_parse_module_section_offsets() {
+        let wasm_data = WasmWriter::serialize_module(&WasmModule {
+            version: 1,
+            sections: vec![
+                Section::new(1, vec![0x01, 0x60, 0x00, 0x00]),
+                Section::custom("padding", &[0u8; 200]),
+                Section::new(3, vec![0x01, 0x00]),
+                Section::new(10, vec![0x01, 0x02, 0x00, 0x0B]),
+            ],
+        })
+        .unwrap();
+
+        let module = WasmParser::parse_module(&wasm_data).unwrap();
+        let offsets: Vec<usize> = module.sections.iter().map(|s| s.offset.unwrap()).collect();
+        assert_eq!(offsets[0], 8);
+        assert!(offsets.windows(2).all(|w| w[0] < w[1]));
+        for section in &module.sections {
+            assert_eq!(wasm_data[section.offset.unwrap()], section.id);
+        }
+
+        let mut truncated = wasm_data.clone();
+        truncated.truncate(offsets[3] + 3);
+        let err = WasmParser::parse_module(&truncated).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&format!("Section 10 at offset 0x{:x}", offsets[3])));
+    }
+
+    #[test]
+    fn test_parse_module_truncated_section() {
+        let wasm_data = vec![
+            0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, // Header
+            0x01, 0x10, 0x01, 0x60, // Type section claiming 16 bytes
+        ];
+
+        assert!(WasmParser::parse_module(&wasm_data).is_err());
+    }
+
+    #[test]
+    fn test_parse_module_malformed_inputs() {
+        let inputs: [&[u8]; 4] = [
+            // Section size of u32::MAX
+            &[
+                0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F,
+            ],
+            // Section size LEB128 that never terminates
+            &[
+                0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, 0x01, 0x80, 0x80, 0x80, 0x80, 0x80,
+                0x80,
+            ],
+            // Section ID with no size
+            &[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, 0x0A],
+            // Truncated header
+            &[0x00, 0x61, 0x73, 0x6D, 0x01],
+        ];
+
+        for input in inputs {
+            assert!(WasmParser::parse_module(input).is_err());
+        }
+
+        // A custom section whose name length overruns it still parses as opaque bytes
+        let module = WasmParser::parse_module(&[
+            0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, 0x00, 0x02, 0x7F, 0x61,
+        ])
+        .unwrap();
+        assert_eq!(module.sections[0].custom_name(), None);
+        assert!(module.custom_section("name").is_none());
+    }
+
+    #[test]
+    fn test_detect_features_simd() {
+        let wasm_data = wat::parse_str("(module (func v128.const i64x2 0 0 drop))").unwrap();
+        let features = WasmParser::detect_features(&wasm_data).unwrap();
+        assert_eq!(features, vec!["simd"]);
+    }
+
+    #[test]
+    fn test_detect_features_threads() {
+        let wasm_data = wat::parse_str("(module (func atomic.fence))").unwrap();
+        let features = WasmParser::detect_features(&wasm_data).unwrap();
+        assert_eq!(features, vec!["threads"]);
+    }
+
+    #[test]
+    fn test_detect_features_mvp() {
+        let wasm_data = wat::parse_str("(module (func))").unwrap();
+        let features = WasmParser::detect_features(&wasm_data).unwrap();
+        assert!(features.is_empty());
+    }
+
     // Test with real WASM file if available
     #[test]
     fn test_parse_real_wasm_file() {
@@ -0,0 +1,316 @@
+use std::time::Instant;
+
+use log::info;
+
+use crate::error::{Result, RusWaCipherError};
+use crate::timing::PhaseTimer;
+use crate::wasm::module::WasmModule;
+use crate::wasm::{globals, names, WasmParser, WasmWriter};
+
+/// Names of the built-in passes accepted by [`transform_by_name`]
+pub const PASS_NAMES: &[&str] = &["scramble_names", "strip_metadata", "obfuscate_globals"];
+
+/// State shared by the transforms of a single pipeline run
+#[derive(Debug, Default)]
+pub struct ObfuscationContext {
+    /// Descriptions of the transforms that changed the module, in run order
+    pub applied: Vec<String>,
+    /// Extra names passes must leave alone, on top of [`names::RESERVED_NAMES`]
+    pub preserve: Vec<String>,
+    /// Seed for randomized passes; when unset it is derived from the module,
+    /// so the same input always produces the same output
+    pub seed: Option<u64>,
+    /// Time spent in each pass, recorded under the pass name
+    pub timer: PhaseTimer,
+}
+
+/// A single obfuscation pass over a module
+pub trait Transform {
+    fn name(&self) -> &str;
+
+    /// Machine-readable identifier plus parameters, e.g. `dead_code{bytes:5}`
+    fn describe(&self) -> String {
+        self.name().to_string()
+    }
+
+    fn apply(&self, module: WasmModule, ctx: &mut ObfuscationContext) -> Result<WasmModule>;
+}
+
+/// Replaces debug names with opaque identifiers, except preserved ones
+/// (see [`names::scramble_names`])
+pub struct ScrambleNames;
+
+impl Transform for ScrambleNames {
+    fn name(&self) -> &str {
+        "scramble_names"
+    }
+
+    fn apply(&self, mut module: WasmModule, ctx: &mut ObfuscationContext) -> Result<WasmModule> {
+        if names::scramble_names(&mut module, &ctx.preserve)? {
+            ctx.applied.push(self.describe());
+        }
+        Ok(module)
+    }
+}
+
+/// Removes debug and toolchain metadata (`name`, `producers`, DWARF, source maps).
+///
+/// Leaves every other section byte-identical, so it cannot change behavior
+/// and never touches exported names.
+pub struct StripMetadata;
+
+impl StripMetadata {
+    fn is_metadata(name: &str) -> bool {
+        matches!(
+            name,
+            "name" | "producers" | "sourceMappingURL" | "external_debug_info"
+        ) || name.starts_with(".debug_")
+    }
+}
+
+impl Transform for StripMetadata {
+    fn name(&self) -> &str {
+        "strip_metadata"
+    }
+
+    fn apply(&self, mut module: WasmModule, ctx: &mut ObfuscationContext) -> Result<WasmModule> {
+        let before = module.sections.len();
+        module
+            .sections
+            .retain(|s| !s.custom_name().is_some_and(Self::is_metadata));
+        let removed = before - module.sections.len();
+
+        if removed > 0 {
+            ctx.applied
+                .push(format!("{}{{sections:{}}}", self.name(), removed));
+        }
+        Ok(module)
+    }
+}
+
+/// Hides constant global initializers behind a subtraction
+/// (see [`globals::obfuscate_globals`])
+pub struct ObfuscateGlobals;
+
+impl Transform for ObfuscateGlobals {
+    fn name(&self) -> &str {
+        "obfuscate_globals"
+    }
+
+    fn apply(&self, mut module: WasmModule, ctx: &mut ObfuscationContext) -> Result<WasmModule> {
+        let rewritten = globals::obfuscate_globals(&mut module, ctx.seed)?;
+        if rewritten > 0 {
+            ctx.applied
+                .push(format!("{}{{globals:{}}}", self.name(), rewritten));
+        }
+        Ok(module)
+    }
+}
+
+/// Look up a built-in pass by name
+pub fn transform_by_name(name: &str) -> Result<Box<dyn Transform>> {
+    match name {
+        "scramble_names" => Ok(Box::new(ScrambleNames)),
+        "strip_metadata" => Ok(Box::new(StripMetadata)),
+        "obfuscate_globals" => Ok(Box::new(ObfuscateGlobals)),
+        _ => Err(RusWaCipherError::InvalidInput(format!(
+            "Unknown pass '{}'; valid passes: {}",
+            name,
+            PASS_NAMES.join(", ")
+        ))),
+    }
+}
+
+/// Parse `wasm`, run a single named pass over it and serialize the result.
+///
+/// Randomized passes draw from `seed`, so equal inputs and seeds give equal output.
+pub fn run_pass(name: &str, wasm: &[u8], seed: u64) -> Result<Vec<u8>> {
+    let transform = transform_by_name(name)?;
+    let module = WasmParser::parse_module(wasm)?;
+    let mut ctx = ObfuscationContext {
+        seed: Some(seed),
+        ..Default::default()
+    };
+    let module = transform.apply(module, &mut ctx)?;
+    WasmWriter::serialize_module(&module)
+}
+
+/// Run the transforms in order, feeding each the previous output.
+///
+/// A module without sections is returned unchanged, so it can still be encrypted.
+/// Otherwise the result is checked with [`WasmModule::validate_indices`].
+pub fn run_transforms(
+    mut module: WasmModule,
+    transforms: &[Box<dyn Transform>],
+    ctx: &mut ObfuscationContext,
+) -> Result<WasmModule> {
+    if module.sections.is_empty() {
+        info!("Module has no sections, skipping obfuscation");
+        return Ok(module);
+    }
+
+    for transform in transforms {
+        info!("Running transform: {}", transform.name());
+        let start = Instant::now();
+        module = transform.apply(module, ctx)?;
+        ctx.timer.record(transform.name(), start.elapsed());
+    }
+
+    if !ctx.applied.is_empty() {
+        info!("Applied transforms: {}", ctx.applied.join(", "));
+    }
+    module.validate_indices()?;
+    Ok(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wasm::module::Section;
+
+    /// Appends a custom section named after itself
+    struct AppendMarker(&'static str);
+
+    impl Transform for AppendMarker {
+        fn name(&self) -> &str {
+            "append_marker"
+        }
+
+        fn describe(&self) -> String {
+            format!("append_marker{{name:{}}}", self.0)
+        }
+
+        fn apply(
+            &self,
+            mut module: WasmModule,
+            ctx: &mut ObfuscationContext,
+        ) -> Result<WasmModule> {
+            module.sections.push(Section::custom(self.0, &[]));
+            ctx.applied.push(self.describe());
+            Ok(module)
+        }
+    }
+
+    #[test]
+    fn test_transforms_run_in_given_order() {
+        let module = WasmModule {
+            version: 1,
+            sections: vec![Section::new(1, vec![0x00])],
+        };
+        let transforms: Vec<Box<dyn Transform>> = vec![
+            Box::new(AppendMarker("second")),
+            Box::new(ScrambleNames),
+            Box::new(AppendMarker("first")),
+        ];
+
+        let mut ctx = ObfuscationContext::default();
+        let module = run_transforms(module, &transforms, &mut ctx).unwrap();
+
+        // ScrambleNames finds no name section and leaves the module untouched
+        assert_eq!(
+            ctx.applied,
+            vec!["append_marker{name:second}", "append_marker{name:first}"]
+        );
+        let names: Vec<_> = module
+            .sections
+            .iter()
+            .filter_map(|s| s.custom_name())
+            .collect();
+        assert_eq!(names, vec!["second", "first"]);
+
+        let phases: Vec<_> = ctx.timer.phases().iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(
+            phases,
+            vec!["append_marker", "scramble_names", "append_marker"]
+        );
+        assert!(ctx.timer.summary().contains("scramble_names "));
+    }
+
+    #[test]
+    fn test_run_pass_by_name() {
+        let wasm_data = wat::parse_str(
+            "(module (global $limit i32 (i32.const 1234)) (func $check_password (param $secret i32)))",
+        )
+        .unwrap();
+
+        for name in PASS_NAMES {
+            let output = run_pass(name, &wasm_data, 7).unwrap();
+            wasmparser::Validator::new().validate_all(&output).unwrap();
+            assert_ne!(output, wasm_data, "{} left the module unchanged", name);
+            assert_eq!(run_pass(name, &wasm_data, 7).unwrap(), output);
+        }
+        assert_ne!(
+            run_pass("obfuscate_globals", &wasm_data, 8).unwrap(),
+            run_pass("obfuscate_globals", &wasm_data, 7).unwrap()
+        );
+
+        let err = run_pass("dead_code", &wasm_data, 0)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown pass 'dead_code'"));
+        assert!(err.contains("scramble_names"));
+    }
+
+    #[test]
+    fn test_strip_metadata_keeps_code() {
+        let wasm_data =
+            wat::parse_str("(module (func $check_password (param $secret i32)))").unwrap();
+        let mut module = WasmParser::parse_module(&wasm_data).unwrap();
+        module.sections.push(Section::custom("producers", &[0x00]));
+        module
+            .sections
+            .push(Section::custom("target_features", &[0x00]));
+        let code = module.section(crate::wasm::SectionType::Code).cloned();
+
+        let mut ctx = ObfuscationContext::default();
+        let output = StripMetadata.apply(module, &mut ctx).unwrap();
+
+        assert_eq!(ctx.applied, vec!["strip_metadata{sections:2}"]);
+        assert!(output.custom_section("name").is_none());
+        assert!(output.custom_section("producers").is_none());
+        assert!(output.custom_section("target_features").is_some());
+        assert_eq!(
+            output.section(crate::wasm::SectionType::Code).cloned(),
+            code
+        );
+    }
+
+    #[test]
+    fn test_sectionless_module_is_noop() {
+        let wasm_data = [0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00];
+        let module = crate::wasm::WasmParser::parse_module(&wasm_data).unwrap();
+        let transforms: Vec<Box<dyn Transform>> =
+            vec![Box::new(ScrambleNames), Box::new(AppendMarker("marker"))];
+
+        let mut ctx = ObfuscationContext::default();
+        let output = run_transforms(module.clone(), &transforms, &mut ctx).unwrap();
+        assert_eq!(output, module);
+        assert!(ctx.applied.is_empty());
+        assert_eq!(
+            crate::wasm::WasmWriter::serialize_module(&output).unwrap(),
+            wasm_data
+        );
+    }
+
+    #[test]
+    fn test_imports_only_module_is_unchanged() {
+        // Only imports: no Function or Code section, so no defined functions to transform
+        let wasm_data = wat::parse_str(
+            r#"(module
+                (import "env" "log" (func (param i32)))
+                (import "env" "memory" (memory 1)))"#,
+        )
+        .unwrap();
+        let module = WasmParser::parse_module(&wasm_data).unwrap();
+        let transforms = PASS_NAMES
+            .iter()
+            .map(|name| transform_by_name(name))
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        let mut ctx = ObfuscationContext::default();
+        let output = run_transforms(module, &transforms, &mut ctx).unwrap();
+        assert!(ctx.applied.is_empty());
+        assert_eq!(WasmWriter::serialize_module(&output).unwrap(), wasm_data);
+    }
+}
@@ -1,4 +1,6 @@
 use crate::error::Result;
+use crate::wasm::leb;
+use crate::wasm::module::WasmModule;
 
 pub struct WasmWriter;
 
@@ -17,6 +19,22 @@ impl WasmWriter {
         crate::wasm::WasmParser::validate_wasm(data)?;
         Self::write_wasm_file(path, data)
     }
+
+    /// Serialize a parsed module back into WASM bytes
+    pub fn serialize_module(module: &WasmModule) -> Result<Vec<u8>> {
+        let mut output = Vec::new();
+        output.extend_from_slice(b"\0asm");
+        output.extend_from_slice(&module.version.to_le_bytes());
+
+        for section in &module.sections {
+            let size = leb::checked_length(section.data.len(), &format!("Section {}", section.id))?;
+            output.push(section.id);
+            leb::write_u32(&mut output, size);
+            output.extend_from_slice(&section.data);
+        }
+
+        Ok(output)
+    }
 }
 
 #[cfg(test)]
@@ -34,4 +52,41 @@ mod tests {
 
         assert_eq!(test_data, written_data.as_slice());
     }
+
+    #[test]
+    fn test_serialize_module_round_trip() {
+        let wasm_data = vec![
+            0x00, 0x61, 0x73, 0x6D, // WASM magic number
+            0x01, 0x00, 0x00, 0x00, // Version
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // Type section
+            0x03, 0x02, 0x01, 0x00, // Function section
+            0x0A, 0x04, 0x01, 0x02, 0x00, 0x0B, // Code section
+        ];
+
+        let module = crate::wasm::WasmParser::parse_module(&wasm_data).unwrap();
+        assert_eq!(WasmWriter::serialize_module(&module).unwrap(), wasm_data);
+    }
+
+    #[test]
+    fn test_unknown_section_round_trip() {
+        let wasm_data = vec![
+            0x00, 0x61, 0x73, 0x6D, // WASM magic number
+            0x01, 0x00, 0x00, 0x00, // Version
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // Type section
+            0x7F, 0x03, 0xAA, 0xBB, 0xCC, // Made-up section 0x7F
+            0x03, 0x02, 0x01, 0x00, // Function section
+        ];
+
+        let module = crate::wasm::WasmParser::parse_module(&wasm_data).unwrap();
+        assert_eq!(
+            module.sections[1].section_type(),
+            crate::wasm::SectionType::Unknown(0x7F)
+        );
+        assert_eq!(module.sections[1].data, vec![0xAA, 0xBB, 0xCC]);
+        assert_eq!(WasmWriter::serialize_module(&module).unwrap(), wasm_data);
+
+        // Transforms pass unknown sections through untouched
+        let output = crate::wasm::run_pass("scramble_names", &wasm_data, 0).unwrap();
+        assert_eq!(output, wasm_data);
+    }
 }
@@ -0,0 +1,19 @@
+// Decrypts a RusWaCipher AES-GCM container with the browser loader's own parsing code.
+// Usage: node decrypt_with_loader.js <loader.js> <encrypted file> <key hex>
+const fs = require('fs');
+
+global.window = { crypto: globalThis.crypto };
+console.log = () => {};
+
+const WasmGuardianLoader = require(process.argv[2]);
+const encrypted = new Uint8Array(fs.readFileSync(process.argv[3]));
+
+new WasmGuardianLoader()
+    ._decryptWasm(encrypted, process.argv[4], 'aes-gcm')
+    .then((plaintext) => {
+        process.stdout.write(Buffer.from(plaintext).toString('hex'));
+    })
+    .catch((error) => {
+        process.stderr.write(`${error.message}\n`);
+        process.exit(1);
+    });
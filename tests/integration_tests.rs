@@ -1,4 +1,5 @@
 use assert_cmd::Command;
+use base64::Engine;
 use predicates::prelude::*;
 use serial_test::serial;
 use std::fs;
@@ -295,3 +296,1103 @@ fn test_key_format_options() {
         .chars()
         .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '='));
 }
+
+#[test]
+#[serial]
+fn test_cli_embed_key_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let encrypted_file = temp_dir.path().join("encrypted.wasm");
+    let decrypted_file = temp_dir.path().join("decrypted.wasm");
+
+    let mut encrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    encrypt_cmd
+        .arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--embed-key");
+
+    encrypt_cmd
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("WARNING"));
+
+    // Decrypt without any external key
+    let mut decrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    decrypt_cmd
+        .arg("decrypt")
+        .arg("-i")
+        .arg(&encrypted_file)
+        .arg("-o")
+        .arg(&decrypted_file);
+
+    decrypt_cmd.assert().success();
+
+    let original_content = fs::read(input_wasm.path()).unwrap();
+    let decrypted_content = fs::read(&decrypted_file).unwrap();
+    assert_eq!(original_content, decrypted_content);
+}
+
+#[test]
+#[serial]
+fn test_cli_decrypt_without_key_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let encrypted_file = temp_dir.path().join("encrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+
+    let mut encrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    encrypt_cmd
+        .arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--generate-key")
+        .arg(&key_file);
+    encrypt_cmd.assert().success();
+
+    let mut decrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    decrypt_cmd
+        .arg("decrypt")
+        .arg("-i")
+        .arg(&encrypted_file)
+        .arg("-o")
+        .arg(temp_dir.path().join("decrypted.wasm"));
+
+    decrypt_cmd
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No key provided"));
+}
+
+#[test]
+#[serial]
+fn test_cli_key_env_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let encrypted_file = temp_dir.path().join("encrypted.wasm");
+    let decrypted_file = temp_dir.path().join("decrypted.wasm");
+    let key = base64::engine::general_purpose::STANDARD.encode([0x5Au8; 32]);
+
+    let mut encrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    encrypt_cmd
+        .env("RUSWACIPHER_CI_KEY", &key)
+        .arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--key-env")
+        .arg("RUSWACIPHER_CI_KEY")
+        .arg("--key-env-format")
+        .arg("base64");
+
+    encrypt_cmd.assert().success();
+
+    let mut decrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    decrypt_cmd
+        .env("RUSWACIPHER_CI_KEY", &key)
+        .arg("decrypt")
+        .arg("-i")
+        .arg(&encrypted_file)
+        .arg("-o")
+        .arg(&decrypted_file)
+        .arg("--key-env")
+        .arg("RUSWACIPHER_CI_KEY")
+        .arg("--key-env-format")
+        .arg("base64");
+
+    decrypt_cmd.assert().success();
+
+    let original_content = fs::read(input_wasm.path()).unwrap();
+    let decrypted_content = fs::read(&decrypted_file).unwrap();
+    assert_eq!(original_content, decrypted_content);
+
+    // An unset variable must fail instead of generating a fresh key
+    let mut missing_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    missing_cmd
+        .env_remove("RUSWACIPHER_CI_KEY")
+        .arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--key-env")
+        .arg("RUSWACIPHER_CI_KEY");
+
+    missing_cmd
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("RUSWACIPHER_CI_KEY is not set"));
+}
+
+#[test]
+#[serial]
+fn test_cli_decrypt_clean_strips_reserved_sections() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = temp_dir.path().join("input.wasm");
+    let encrypted_file = temp_dir.path().join("encrypted.wasm");
+    let decrypted_file = temp_dir.path().join("decrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+
+    // Minimal module plus a `.rwc.vm` custom section
+    let mut wasm_data = fs::read(create_test_wasm_file().path()).unwrap();
+    wasm_data.extend_from_slice(&[0x00, 0x09, 0x07]);
+    wasm_data.extend_from_slice(b".rwc.vm");
+    wasm_data.push(0xAA);
+    fs::write(&input_wasm, &wasm_data).unwrap();
+
+    let mut encrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    encrypt_cmd
+        .arg("encrypt")
+        .arg("-i")
+        .arg(&input_wasm)
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--generate-key")
+        .arg(&key_file);
+    encrypt_cmd.assert().success();
+
+    let mut decrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    decrypt_cmd
+        .arg("decrypt")
+        .arg("-i")
+        .arg(&encrypted_file)
+        .arg("-o")
+        .arg(&decrypted_file)
+        .arg("-k")
+        .arg(&key_file)
+        .arg("--clean");
+    decrypt_cmd.assert().success();
+
+    let decrypted_content = fs::read(&decrypted_file).unwrap();
+    assert_eq!(
+        decrypted_content,
+        fs::read(create_test_wasm_file().path()).unwrap()
+    );
+}
+
+#[test]
+#[serial]
+fn test_cli_encrypt_json_output() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let output_file = temp_dir.path().join("encrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    let output = cmd
+        .arg("--format")
+        .arg("json")
+        .arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&output_file)
+        .arg("--generate-key")
+        .arg(&key_file)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["command"], "encrypt");
+    assert_eq!(report["algorithm"], "aes-256-gcm");
+    assert_eq!(report["input_size"], 24);
+    assert_eq!(
+        report["output_size"],
+        fs::metadata(&output_file).unwrap().len()
+    );
+    assert_eq!(
+        report["generated_key"],
+        fs::read_to_string(&key_file).unwrap().trim()
+    );
+}
+
+#[test]
+#[serial]
+fn test_cli_encrypt_json_reports_features() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wat = temp_dir.path().join("simd.wat");
+    fs::write(&input_wat, "(module (func v128.const i64x2 0 0 drop))").unwrap();
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    let output = cmd
+        .arg("--format")
+        .arg("json")
+        .arg("encrypt")
+        .arg("-i")
+        .arg(&input_wat)
+        .arg("-o")
+        .arg(temp_dir.path().join("encrypted.wasm"))
+        .arg("--generate-key")
+        .arg(temp_dir.path().join("test.key"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["features"], serde_json::json!(["simd"]));
+}
+
+#[test]
+#[serial]
+fn test_cli_encrypt_wat_input() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wat = temp_dir.path().join("answer.wat");
+    let encrypted_file = temp_dir.path().join("encrypted.wasm");
+    let decrypted_file = temp_dir.path().join("decrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+
+    let source = r#"(module (func (export "answer") (result i32) i32.const 42))"#;
+    fs::write(&input_wat, source).unwrap();
+
+    let mut encrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    encrypt_cmd
+        .arg("encrypt")
+        .arg("-i")
+        .arg(&input_wat)
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--generate-key")
+        .arg(&key_file);
+    encrypt_cmd.assert().success();
+
+    let mut decrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    decrypt_cmd
+        .arg("decrypt")
+        .arg("-i")
+        .arg(&encrypted_file)
+        .arg("-o")
+        .arg(&decrypted_file)
+        .arg("-k")
+        .arg(&key_file);
+    decrypt_cmd.assert().success();
+
+    let decrypted_content = fs::read(&decrypted_file).unwrap();
+    assert_eq!(decrypted_content, wat::parse_str(source).unwrap());
+}
+
+#[test]
+#[serial]
+fn test_cli_version_command() {
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("version")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(env!("CARGO_PKG_VERSION")))
+        .stdout(predicate::str::contains("commit"));
+}
+
+#[test]
+#[serial]
+fn test_cli_envelope_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let encrypted_file = temp_dir.path().join("encrypted.wasm");
+    let decrypted_file = temp_dir.path().join("decrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+
+    let mut encrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    encrypt_cmd
+        .arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--generate-key")
+        .arg(&key_file)
+        .arg("--envelope");
+    encrypt_cmd.assert().success();
+
+    // The envelope itself must be a valid WASM module
+    let envelope = fs::read(&encrypted_file).unwrap();
+    assert!(ruswacipher::wasm::WasmParser::validate_wasm(&envelope).is_ok());
+
+    let mut decrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    decrypt_cmd
+        .arg("decrypt")
+        .arg("-i")
+        .arg(&encrypted_file)
+        .arg("-o")
+        .arg(&decrypted_file)
+        .arg("-k")
+        .arg(&key_file);
+    decrypt_cmd.assert().success();
+
+    let original_content = fs::read(input_wasm.path()).unwrap();
+    let decrypted_content = fs::read(&decrypted_file).unwrap();
+    assert_eq!(original_content, decrypted_content);
+
+    // The JSON report sizes the file that was read, not the unwrapped container
+    let mut decrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    let output = decrypt_cmd
+        .arg("--format")
+        .arg("json")
+        .arg("decrypt")
+        .arg("-i")
+        .arg(&encrypted_file)
+        .arg("-o")
+        .arg(&decrypted_file)
+        .arg("-k")
+        .arg(&key_file)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["input_size"], envelope.len());
+    assert_eq!(report["output_size"], original_content.len());
+}
+
+#[test]
+#[serial]
+fn test_cli_encrypt_print_sri() {
+    use sha2::{Digest, Sha256, Sha384};
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let output_file = temp_dir.path().join("encrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    let output = cmd
+        .arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&output_file)
+        .arg("--generate-key")
+        .arg(&key_file)
+        .arg("--print-sri")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let encrypted = fs::read(&output_file).unwrap();
+    let expected = format!(
+        "sha384-{}",
+        base64::engine::general_purpose::STANDARD.encode(Sha384::digest(&encrypted))
+    );
+    assert_eq!(String::from_utf8(output.stdout).unwrap().trim(), expected);
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    let output = cmd
+        .arg("--format")
+        .arg("json")
+        .arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&output_file)
+        .arg("-k")
+        .arg(&key_file)
+        .arg("--print-sri")
+        .arg("sha256")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let encrypted = fs::read(&output_file).unwrap();
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        report["integrity"],
+        format!(
+            "sha256-{}",
+            base64::engine::general_purpose::STANDARD.encode(Sha256::digest(&encrypted))
+        )
+    );
+}
+
+#[test]
+#[serial]
+fn test_cli_encrypt_strip_only() {
+    use ruswacipher::wasm::{SectionType, WasmParser};
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("input.wasm");
+    let encrypted_file = temp_dir.path().join("encrypted.wasm");
+    let decrypted_file = temp_dir.path().join("decrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+
+    let input = wat::parse_str("(module (func $check_password (param $secret i32)))").unwrap();
+    fs::write(&input_file, &input).unwrap();
+
+    let mut encrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    encrypt_cmd
+        .arg("encrypt")
+        .arg("-i")
+        .arg(&input_file)
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--generate-key")
+        .arg(&key_file)
+        .arg("--strip-only");
+    encrypt_cmd.assert().success();
+
+    let mut decrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    decrypt_cmd
+        .arg("decrypt")
+        .arg("-i")
+        .arg(&encrypted_file)
+        .arg("-o")
+        .arg(&decrypted_file)
+        .arg("-k")
+        .arg(&key_file);
+    decrypt_cmd.assert().success();
+
+    let original = WasmParser::parse_module(&input).unwrap();
+    let stripped = WasmParser::parse_module(&fs::read(&decrypted_file).unwrap()).unwrap();
+    assert!(original.custom_section("name").is_some());
+    assert!(stripped.custom_section("name").is_none());
+    assert_eq!(
+        stripped.section(SectionType::Code).map(|s| &s.data),
+        original.section(SectionType::Code).map(|s| &s.data)
+    );
+}
+
+#[test]
+#[serial]
+fn test_cli_sign_and_verify() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let encrypted_file = temp_dir.path().join("encrypted.wasm");
+    let decrypted_file = temp_dir.path().join("decrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+    let sign_key_file = temp_dir.path().join("sign.key");
+    let verify_key_file = temp_dir.path().join("verify.pub");
+
+    let seed = [9u8; 32];
+    let public_key = ed25519_dalek::SigningKey::from_bytes(&seed)
+        .verifying_key()
+        .to_bytes();
+    fs::write(&sign_key_file, hex::encode(seed)).unwrap();
+    fs::write(&verify_key_file, hex::encode(public_key)).unwrap();
+
+    let mut encrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    encrypt_cmd
+        .arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--generate-key")
+        .arg(&key_file)
+        .arg("--sign-key")
+        .arg(&sign_key_file);
+    encrypt_cmd.assert().success();
+    assert_eq!(
+        fs::read(temp_dir.path().join("encrypted.wasm.sig"))
+            .unwrap()
+            .len(),
+        64
+    );
+
+    let decrypt = || {
+        let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+        cmd.arg("decrypt")
+            .arg("-i")
+            .arg(&encrypted_file)
+            .arg("-o")
+            .arg(&decrypted_file)
+            .arg("-k")
+            .arg(&key_file)
+            .arg("--verify-key")
+            .arg(&verify_key_file);
+        cmd
+    };
+    decrypt().assert().success();
+    assert_eq!(
+        fs::read(&decrypted_file).unwrap(),
+        fs::read(input_wasm.path()).unwrap()
+    );
+
+    // Tamper with the container: the signature check fails before decryption
+    let mut tampered = fs::read(&encrypted_file).unwrap();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    fs::write(&encrypted_file, tampered).unwrap();
+    decrypt()
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Signature verification failed"));
+}
+
+#[test]
+#[serial]
+fn test_cli_encrypt_notice() {
+    use ruswacipher::wasm::{WasmParser, NOTICE_SECTION};
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let notice_file = temp_dir.path().join("NOTICE");
+    let encrypted_file = temp_dir.path().join("encrypted.wasm");
+    let decrypted_file = temp_dir.path().join("decrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+
+    let notice = "Copyright (c) Example Corp. All rights reserved.";
+    fs::write(&notice_file, notice).unwrap();
+
+    let mut encrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    encrypt_cmd
+        .arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--generate-key")
+        .arg(&key_file)
+        .arg("--notice")
+        .arg(&notice_file);
+    encrypt_cmd.assert().success();
+
+    let mut decrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    decrypt_cmd
+        .arg("decrypt")
+        .arg("-i")
+        .arg(&encrypted_file)
+        .arg("-o")
+        .arg(&decrypted_file)
+        .arg("-k")
+        .arg(&key_file);
+    decrypt_cmd.assert().success();
+
+    let module = WasmParser::parse_module(&fs::read(&decrypted_file).unwrap()).unwrap();
+    let section = module.custom_section(NOTICE_SECTION).unwrap();
+    assert_eq!(section.custom_content(), Some(notice.as_bytes()));
+}
+
+#[test]
+#[serial]
+fn test_cli_generated_key_defaults_to_output_key() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let output_file = temp_dir.path().join("encrypted.wasm");
+    let default_key = temp_dir.path().join("encrypted.wasm.key");
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&output_file);
+    cmd.assert().success();
+    assert!(default_key.exists());
+
+    let decrypted_file = temp_dir.path().join("decrypted.wasm");
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("decrypt")
+        .arg("-i")
+        .arg(&output_file)
+        .arg("-o")
+        .arg(&decrypted_file)
+        .arg("-k")
+        .arg(&default_key);
+    cmd.assert().success();
+    assert_eq!(
+        fs::read(&decrypted_file).unwrap(),
+        fs::read(input_wasm.path()).unwrap()
+    );
+}
+
+#[test]
+#[serial]
+fn test_cli_refuses_to_overwrite_key_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let output_file = temp_dir.path().join("encrypted.wasm");
+    let key_file = temp_dir.path().join("existing.key");
+    fs::write(&key_file, "keep me").unwrap();
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&output_file)
+        .arg("--generate-key")
+        .arg(&key_file);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"))
+        .stderr(predicate::str::contains("--force-key"));
+    assert_eq!(fs::read_to_string(&key_file).unwrap(), "keep me");
+    assert!(!output_file.exists());
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&output_file)
+        .arg("--generate-key")
+        .arg(&key_file)
+        .arg("--force-key");
+    cmd.assert().success();
+    assert_ne!(fs::read_to_string(&key_file).unwrap(), "keep me");
+}
+
+#[test]
+#[serial]
+fn test_cli_split_size_parts_decrypt() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let output_file = temp_dir.path().join("encrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+
+    // 24-byte module + 12-byte nonce + 16-byte tag = 52 bytes, so three parts of up to 20
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&output_file)
+        .arg("--generate-key")
+        .arg(&key_file)
+        .arg("--split-size")
+        .arg("20");
+    cmd.assert().success();
+
+    let parts: Vec<_> = (0..3)
+        .map(|i| temp_dir.path().join(format!("encrypted.wasm.{:03}", i)))
+        .collect();
+    assert!(parts.iter().all(|p| p.exists()));
+    assert!(!output_file.exists());
+    assert!(!temp_dir.path().join("encrypted.wasm.003").exists());
+
+    let decrypted_file = temp_dir.path().join("decrypted.wasm");
+    let part_refs: Vec<_> = parts.iter().map(|p| p.as_path()).collect();
+    ruswacipher::crypto::decrypt_parts(&part_refs, &key_file, &decrypted_file).unwrap();
+    assert_eq!(
+        fs::read(&decrypted_file).unwrap(),
+        fs::read(input_wasm.path()).unwrap()
+    );
+
+    let reordered = [part_refs[1], part_refs[0], part_refs[2]];
+    assert!(ruswacipher::crypto::decrypt_parts(&reordered, &key_file, &decrypted_file).is_err());
+
+    let cli_decrypted_file = temp_dir.path().join("cli_decrypted.wasm");
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("decrypt")
+        .arg("-i")
+        .arg(&output_file)
+        .arg("-o")
+        .arg(&cli_decrypted_file)
+        .arg("-k")
+        .arg(&key_file)
+        .arg("--parts");
+    cmd.assert().success();
+    assert_eq!(
+        fs::read(&cli_decrypted_file).unwrap(),
+        fs::read(input_wasm.path()).unwrap()
+    );
+
+    fs::remove_file(&parts[1]).unwrap();
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("decrypt")
+        .arg("-i")
+        .arg(&output_file)
+        .arg("-o")
+        .arg(&cli_decrypted_file)
+        .arg("-k")
+        .arg(&key_file)
+        .arg("--parts");
+    cmd.assert().failure();
+}
+
+#[test]
+#[serial]
+fn test_cli_split_size_removes_stale_parts() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let output_file = temp_dir.path().join("encrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+
+    for (split_size, force_key) in [("20", false), ("100", true)] {
+        let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+        cmd.arg("encrypt")
+            .arg("-i")
+            .arg(input_wasm.path())
+            .arg("-o")
+            .arg(&output_file)
+            .arg("--generate-key")
+            .arg(&key_file)
+            .arg("--split-size")
+            .arg(split_size);
+        if force_key {
+            cmd.arg("--force-key");
+        }
+        cmd.assert().success();
+    }
+
+    assert!(temp_dir.path().join("encrypted.wasm.000").exists());
+    assert!(!temp_dir.path().join("encrypted.wasm.001").exists());
+    assert!(!temp_dir.path().join("encrypted.wasm.002").exists());
+
+    let decrypted_file = temp_dir.path().join("decrypted.wasm");
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("decrypt")
+        .arg("-i")
+        .arg(&output_file)
+        .arg("-o")
+        .arg(&decrypted_file)
+        .arg("-k")
+        .arg(&key_file)
+        .arg("--parts");
+    cmd.assert().success();
+    assert_eq!(
+        fs::read(&decrypted_file).unwrap(),
+        fs::read(input_wasm.path()).unwrap()
+    );
+}
+
+#[test]
+#[serial]
+fn test_cli_split_size_refuses_sign_and_sri() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+
+    for extra in [["--sign-key", "signing.key"], ["--print-sri", "sha384"]] {
+        let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+        cmd.arg("encrypt")
+            .arg("-i")
+            .arg(input_wasm.path())
+            .arg("-o")
+            .arg(temp_dir.path().join("encrypted.wasm"))
+            .arg("--split-size")
+            .arg("20")
+            .args(extra);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+    assert!(!temp_dir.path().join("encrypted.wasm.000").exists());
+}
+
+#[test]
+#[serial]
+fn test_cli_min_obfuscation_size_skips_transforms() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wat = temp_dir.path().join("tiny.wat");
+    fs::write(&input_wat, "(module (func $check_password))").unwrap();
+
+    let encrypt = |output: &std::path::Path, min_size: &str| {
+        let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+        let output = cmd
+            .arg("--format")
+            .arg("json")
+            .arg("encrypt")
+            .arg("-i")
+            .arg(&input_wat)
+            .arg("-o")
+            .arg(output)
+            .arg("--scramble-names")
+            .arg("--min-obfuscation-size")
+            .arg(min_size)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+    };
+
+    let report = encrypt(&temp_dir.path().join("skipped.wasm"), "4096");
+    assert_eq!(report["obfuscation"], serde_json::json!([]));
+    assert!(temp_dir.path().join("skipped.wasm").exists());
+
+    let report = encrypt(&temp_dir.path().join("scrambled.wasm"), "0");
+    assert_eq!(report["obfuscation"], serde_json::json!(["scramble_names"]));
+}
+
+#[test]
+#[serial]
+fn test_cli_round_trip_preserves_section_order() {
+    use ruswacipher::wasm::WasmParser;
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_file = temp_dir.path().join("input.wasm");
+    let encrypted_file = temp_dir.path().join("encrypted.wasm");
+    let decrypted_file = temp_dir.path().join("decrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+
+    // Custom sections between known ones, where reordering would be visible
+    let mut input = wat::parse_str(
+        r#"(module
+            (memory (export "memory") 1)
+            (data (i32.const 8) "hello")
+            (func (export "answer") (result i32) i32.const 42))"#,
+    )
+    .unwrap();
+    let mut module = WasmParser::parse_module(&input).unwrap();
+    module
+        .sections
+        .insert(1, ruswacipher::wasm::Section::custom("first", b"1"));
+    module
+        .sections
+        .push(ruswacipher::wasm::Section::custom("last", b"2"));
+    input = ruswacipher::wasm::WasmWriter::serialize_module(&module).unwrap();
+    fs::write(&input_file, &input).unwrap();
+
+    let mut encrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    encrypt_cmd
+        .arg("encrypt")
+        .arg("-i")
+        .arg(&input_file)
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--generate-key")
+        .arg(&key_file);
+    encrypt_cmd.assert().success();
+
+    let mut decrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    decrypt_cmd
+        .arg("decrypt")
+        .arg("-i")
+        .arg(&encrypted_file)
+        .arg("-o")
+        .arg(&decrypted_file)
+        .arg("-k")
+        .arg(&key_file);
+    decrypt_cmd.assert().success();
+
+    let section_ids = |data: &[u8]| -> Vec<(u8, Option<String>)> {
+        WasmParser::parse_module(data)
+            .unwrap()
+            .sections
+            .iter()
+            .map(|s| (s.id, s.custom_name().map(str::to_string)))
+            .collect()
+    };
+    let decrypted = fs::read(&decrypted_file).unwrap();
+    assert_eq!(section_ids(&decrypted), section_ids(&input));
+    assert_eq!(decrypted, input);
+}
+
+#[cfg(feature = "http")]
+#[test]
+#[serial]
+fn test_cli_decrypt_from_url() {
+    use std::io::{Read, Write};
+
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let encrypted_file = temp_dir.path().join("encrypted.wasm");
+    let decrypted_file = temp_dir.path().join("decrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+
+    let mut encrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    encrypt_cmd
+        .arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--generate-key")
+        .arg(&key_file);
+    encrypt_cmd.assert().success();
+
+    // Serve the encrypted file once over plain HTTP
+    let body = fs::read(&encrypted_file).unwrap();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/app.wasm.enc", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = stream.read(&mut buf).unwrap();
+            request.extend_from_slice(&buf[..n]);
+        }
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        )
+        .unwrap();
+        stream.write_all(&body).unwrap();
+    });
+
+    let mut decrypt_cmd = Command::cargo_bin("ruswacipher").unwrap();
+    decrypt_cmd
+        .arg("decrypt")
+        .arg("-i")
+        .arg(&url)
+        .arg("-o")
+        .arg(&decrypted_file)
+        .arg("-k")
+        .arg(&key_file);
+    decrypt_cmd.assert().success();
+    server.join().unwrap();
+
+    assert_eq!(
+        fs::read(&decrypted_file).unwrap(),
+        fs::read(input_wasm.path()).unwrap()
+    );
+}
+
+#[cfg(not(feature = "http"))]
+#[test]
+#[serial]
+fn test_cli_decrypt_url_requires_http_feature() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("decrypt")
+        .arg("-i")
+        .arg("https://example.invalid/app.wasm.enc")
+        .arg("-o")
+        .arg(temp_dir.path().join("decrypted.wasm"))
+        .arg("-k")
+        .arg(temp_dir.path().join("missing.key"));
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--features http"));
+}
+
+#[test]
+#[serial]
+fn test_cli_exit_codes() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let encrypted_file = temp_dir.path().join("encrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+    let wrong_key_file = temp_dir.path().join("wrong.key");
+    fs::write(&wrong_key_file, "11".repeat(32)).unwrap();
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--generate-key")
+        .arg(&key_file);
+    cmd.assert().success();
+
+    // Authentication failure
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("decrypt")
+        .arg("-i")
+        .arg(&encrypted_file)
+        .arg("-o")
+        .arg(temp_dir.path().join("decrypted.wasm"))
+        .arg("-k")
+        .arg(&wrong_key_file);
+    cmd.assert()
+        .code(9)
+        .stderr(predicate::str::contains("check that the key"));
+
+    // Missing key file
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("decrypt")
+        .arg("-i")
+        .arg(&encrypted_file)
+        .arg("-o")
+        .arg(temp_dir.path().join("decrypted.wasm"))
+        .arg("-k")
+        .arg(temp_dir.path().join("missing.key"));
+    cmd.assert()
+        .code(6)
+        .stderr(predicate::str::contains("Cannot read key file"));
+
+    // Missing input file
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("decrypt")
+        .arg("-i")
+        .arg(temp_dir.path().join("missing.wasm"))
+        .arg("-o")
+        .arg(temp_dir.path().join("decrypted.wasm"))
+        .arg("-k")
+        .arg(&key_file);
+    cmd.assert().code(3);
+}
+
+#[test]
+#[serial]
+fn test_cli_insecure_requires_url_input() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+    let encrypted_file = temp_dir.path().join("encrypted.wasm");
+    let key_file = temp_dir.path().join("test.key");
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(&encrypted_file)
+        .arg("--generate-key")
+        .arg(&key_file);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("decrypt")
+        .arg("-i")
+        .arg(&encrypted_file)
+        .arg("-o")
+        .arg(temp_dir.path().join("decrypted.wasm"))
+        .arg("-k")
+        .arg(&key_file)
+        .arg("--insecure");
+    cmd.assert()
+        .failure()
+        .code(7)
+        .stderr(predicate::str::contains("--insecure only applies"));
+}
+
+#[test]
+#[serial]
+fn test_cli_diff_reports_when_nothing_changed() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(temp_dir.path().join("encrypted.wasm"))
+        .arg("--generate-key")
+        .arg(temp_dir.path().join("test.key"))
+        .arg("--diff");
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("Diff: no obfuscation pass ran"));
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(temp_dir.path().join("scrambled.wasm"))
+        .arg("--generate-key")
+        .arg(temp_dir.path().join("scrambled.key"))
+        .arg("--scramble-names")
+        .arg("--diff");
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("Diff: no changes"));
+}
+
+#[test]
+#[serial]
+fn test_cli_timing_summary_lists_each_pass() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_wasm = create_test_wasm_file();
+
+    let mut cmd = Command::cargo_bin("ruswacipher").unwrap();
+    cmd.arg("encrypt")
+        .arg("-i")
+        .arg(input_wasm.path())
+        .arg("-o")
+        .arg(temp_dir.path().join("encrypted.wasm"))
+        .arg("--generate-key")
+        .arg(temp_dir.path().join("test.key"))
+        .arg("--scramble-names")
+        .arg("--obfuscate-globals");
+    cmd.assert().success().stderr(
+        predicate::str::is_match("Timing: .*scramble_names [0-9.]+ms, obfuscate_globals [0-9.]+ms")
+            .unwrap(),
+    );
+}
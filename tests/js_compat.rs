@@ -0,0 +1,41 @@
+//! Checks that containers written by the Rust encryptor are readable by the
+//! browser loader in `web/wasmGuardianLoader.js`. Requires Node.js 18+.
+#![cfg(feature = "node")]
+
+use std::path::Path;
+use std::process::Command;
+
+use ruswacipher::config::EncryptionAlgorithm;
+use ruswacipher::crypto;
+use tempfile::TempDir;
+
+#[test]
+fn test_js_loader_decrypts_rust_container() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let temp_dir = TempDir::new().unwrap();
+    let encrypted_file = temp_dir.path().join("module.wasm.enc");
+
+    let plaintext = std::fs::read(root.join("web/test.wasm")).unwrap();
+    let algorithm = EncryptionAlgorithm::AesGcm;
+    let key = crypto::generate_key(&algorithm).unwrap();
+    let encrypted = crypto::encrypt_data(&plaintext, &key, &algorithm).unwrap();
+    std::fs::write(&encrypted_file, encrypted).unwrap();
+
+    let output = Command::new("node")
+        .arg(root.join("tests/compat/decrypt_with_loader.js"))
+        .arg(root.join("web/wasmGuardianLoader.js"))
+        .arg(&encrypted_file)
+        .arg(hex::encode(&key))
+        .output()
+        .expect("failed to run node");
+
+    assert!(
+        output.status.success(),
+        "loader failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        hex::encode(plaintext)
+    );
+}
@@ -1,7 +1,7 @@
 #!/bin/bash
 
 # Build script for WASM decryption helper
-# This script builds the WASM helper module for ChaCha20-Poly1305 decryption
+# This script builds the WASM helper module for ChaCha20-Poly1305 and AES-GCM decryption
 
 set -e
 
@@ -40,6 +40,7 @@ cat > ../web/wasm-decryptor-helper-wrapper.js << 'EOF'
  */
 
 import init, {
+    decrypt_aes_gcm,
     decrypt_chacha20poly1305,
     encrypt_chacha20poly1305,
     get_helper_info,
@@ -76,6 +77,17 @@ export async function decryptChaCha20Poly1305(key, nonce, ciphertext) {
     return decrypt_chacha20poly1305(key, nonce, ciphertext);
 }
 
+/**
+ * Decrypt data using AES-GCM (fallback when SubtleCrypto is unavailable)
+ */
+export async function decryptAesGcm(key, nonce, ciphertext) {
+    if (!isInitialized) {
+        await initWasmHelper();
+    }
+
+    return decrypt_aes_gcm(key, nonce, ciphertext);
+}
+
 /**
  * Encrypt data using ChaCha20-Poly1305 (for testing)
  */
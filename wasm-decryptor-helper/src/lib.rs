@@ -3,6 +3,11 @@ use chacha20poly1305::{
     aead::{Aead, KeyInit},
     ChaCha20Poly1305, Nonce, Key,
 };
+use aes_gcm::{
+    aead::consts::U12,
+    aes::Aes192,
+    Aes128Gcm, Aes256Gcm, AesGcm,
+};
 
 // Import console.log for debugging
 #[wasm_bindgen]
@@ -97,6 +102,65 @@ pub fn decrypt_chacha20poly1305(
     }
 }
 
+/// Decrypt data using AES-GCM, for environments without WebCrypto
+/// 
+/// # Arguments
+/// * `key` - 16-, 24- or 32-byte encryption key
+/// * `nonce` - 12-byte nonce
+/// * `ciphertext` - Encrypted data
+/// 
+/// # Returns
+/// Decrypted data as Uint8Array
+#[wasm_bindgen]
+pub fn decrypt_aes_gcm(
+    key: &[u8],
+    nonce: &[u8],
+    ciphertext: &[u8]
+) -> Result<Vec<u8>, JsValue> {
+    console_log!(
+        "AES-GCM decrypt: key_len={}, nonce_len={}, ciphertext_len={}", 
+        key.len(), 
+        nonce.len(), 
+        ciphertext.len()
+    );
+
+    // Validate nonce length
+    if nonce.len() != 12 {
+        return Err(JsValue::from_str(&format!(
+            "Invalid nonce length: expected 12 bytes, got {}", 
+            nonce.len()
+        )));
+    }
+    let cipher_nonce = aes_gcm::Nonce::from_slice(nonce);
+
+    // Pick the AES variant from the key length
+    let result = match key.len() {
+        16 => Aes128Gcm::new_from_slice(key).map(|c| c.decrypt(cipher_nonce, ciphertext)),
+        24 => AesGcm::<Aes192, U12>::new_from_slice(key).map(|c| c.decrypt(cipher_nonce, ciphertext)),
+        32 => Aes256Gcm::new_from_slice(key).map(|c| c.decrypt(cipher_nonce, ciphertext)),
+        _ => {
+            return Err(JsValue::from_str(&format!(
+                "Invalid key length: expected 16, 24 or 32 bytes, got {}", 
+                key.len()
+            )));
+        }
+    }
+    .map_err(|_| JsValue::from_str("Failed to create AES-GCM cipher"))?;
+
+    // Decrypt
+    match result {
+        Ok(plaintext) => {
+            console_log!("AES-GCM decryption successful: {} bytes", plaintext.len());
+            Ok(plaintext)
+        }
+        Err(e) => {
+            let error_msg = format!("AES-GCM decryption failed: {:?}", e);
+            console_log!("{}", error_msg);
+            Err(JsValue::from_str(&error_msg))
+        }
+    }
+}
+
 /// Encrypt data using ChaCha20-Poly1305 (for testing purposes)
 /// 
 /// # Arguments
@@ -166,7 +230,7 @@ pub fn get_helper_info() -> JsValue {
     let info = serde_json::json!({
         "name": "wasm-decryptor-helper",
         "version": "0.1.0",
-        "supported_algorithms": ["chacha20poly1305"],
+        "supported_algorithms": ["chacha20poly1305", "aes-gcm"],
         "key_length": 32,
         "nonce_length": 12
     });
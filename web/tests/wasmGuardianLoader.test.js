@@ -218,6 +218,32 @@ describe('WasmGuardianLoader', () => {
 
             window.crypto = originalCrypto;
         });
+
+        test('should fall back to the WASM helper without SubtleCrypto', async () => {
+            const originalCrypto = window.crypto;
+            delete window.crypto;
+
+            const plaintext = createMinimalWasm();
+            window.wasmDecryptorHelper = {
+                decrypt_aes_gcm: jest.fn().mockReturnValue(plaintext)
+            };
+
+            const encryptedData = createMockEncryptedWasm(plaintext);
+            const hexKey = '0'.repeat(64);
+
+            const result = await loader._decryptAesGcm(encryptedData, hexKey);
+
+            expect(result).toBe(plaintext);
+            expect(window.wasmDecryptorHelper.decrypt_aes_gcm).toHaveBeenCalledWith(
+                expect.any(Uint8Array),
+                encryptedData.slice(0, 12),
+                encryptedData.slice(12)
+            );
+            expect(console.warn).toHaveBeenCalledWith(expect.stringContaining('slower'));
+
+            delete window.wasmDecryptorHelper;
+            window.crypto = originalCrypto;
+        });
     });
 
     describe('WASM Instantiation', () => {
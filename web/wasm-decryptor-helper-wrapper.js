@@ -4,6 +4,7 @@
  */
 
 import init, {
+    decrypt_aes_gcm,
     decrypt_chacha20poly1305,
     encrypt_chacha20poly1305,
     get_helper_info,
@@ -40,6 +41,17 @@ export async function decryptChaCha20Poly1305(key, nonce, ciphertext) {
     return decrypt_chacha20poly1305(key, nonce, ciphertext);
 }
 
+/**
+ * Decrypt data using AES-GCM (fallback when SubtleCrypto is unavailable)
+ */
+export async function decryptAesGcm(key, nonce, ciphertext) {
+    if (!isInitialized) {
+        await initWasmHelper();
+    }
+
+    return decrypt_aes_gcm(key, nonce, ciphertext);
+}
+
 /**
  * Encrypt data using ChaCha20-Poly1305 (for testing)
  */
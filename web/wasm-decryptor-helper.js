@@ -82,12 +82,6 @@ function getDataViewMemory0() {
     }
     return cachedDataViewMemory0;
 }
-/**
- * Initialize the WASM module
- */
-export function init() {
-    wasm.init();
-}
 
 function passArray8ToWasm0(arg, malloc) {
     const ptr = malloc(arg.length * 1, 1) >>> 0;
@@ -138,28 +132,46 @@ export function decrypt_chacha20poly1305(key, nonce, ciphertext) {
 }
 
 /**
- * Encrypt data using ChaCha20-Poly1305 (for testing purposes)
+ * Test function to verify the helper is working
+ * @returns {boolean}
+ */
+export function test_helper() {
+    const ret = wasm.test_helper();
+    return ret !== 0;
+}
+
+/**
+ * Get information about the WASM decryption helper
+ * @returns {any}
+ */
+export function get_helper_info() {
+    const ret = wasm.get_helper_info();
+    return ret;
+}
+
+/**
+ * Decrypt data using AES-GCM, for environments without WebCrypto
  *
  * # Arguments
- * * `key` - 32-byte encryption key
+ * * `key` - 16-, 24- or 32-byte encryption key
  * * `nonce` - 12-byte nonce
- * * `plaintext` - Data to encrypt
+ * * `ciphertext` - Encrypted data
  *
  * # Returns
- * Encrypted data as Uint8Array
+ * Decrypted data as Uint8Array
  * @param {Uint8Array} key
  * @param {Uint8Array} nonce
- * @param {Uint8Array} plaintext
+ * @param {Uint8Array} ciphertext
  * @returns {Uint8Array}
  */
-export function encrypt_chacha20poly1305(key, nonce, plaintext) {
+export function decrypt_aes_gcm(key, nonce, ciphertext) {
     const ptr0 = passArray8ToWasm0(key, wasm.__wbindgen_malloc);
     const len0 = WASM_VECTOR_LEN;
     const ptr1 = passArray8ToWasm0(nonce, wasm.__wbindgen_malloc);
     const len1 = WASM_VECTOR_LEN;
-    const ptr2 = passArray8ToWasm0(plaintext, wasm.__wbindgen_malloc);
+    const ptr2 = passArray8ToWasm0(ciphertext, wasm.__wbindgen_malloc);
     const len2 = WASM_VECTOR_LEN;
-    const ret = wasm.encrypt_chacha20poly1305(ptr0, len0, ptr1, len1, ptr2, len2);
+    const ret = wasm.decrypt_aes_gcm(ptr0, len0, ptr1, len1, ptr2, len2);
     if (ret[3]) {
         throw takeFromExternrefTable0(ret[2]);
     }
@@ -169,21 +181,41 @@ export function encrypt_chacha20poly1305(key, nonce, plaintext) {
 }
 
 /**
- * Get information about the WASM decryption helper
- * @returns {any}
+ * Initialize the WASM module
  */
-export function get_helper_info() {
-    const ret = wasm.get_helper_info();
-    return ret;
+export function init() {
+    wasm.init();
 }
 
 /**
- * Test function to verify the helper is working
- * @returns {boolean}
+ * Encrypt data using ChaCha20-Poly1305 (for testing purposes)
+ *
+ * # Arguments
+ * * `key` - 32-byte encryption key
+ * * `nonce` - 12-byte nonce
+ * * `plaintext` - Data to encrypt
+ *
+ * # Returns
+ * Encrypted data as Uint8Array
+ * @param {Uint8Array} key
+ * @param {Uint8Array} nonce
+ * @param {Uint8Array} plaintext
+ * @returns {Uint8Array}
  */
-export function test_helper() {
-    const ret = wasm.test_helper();
-    return ret !== 0;
+export function encrypt_chacha20poly1305(key, nonce, plaintext) {
+    const ptr0 = passArray8ToWasm0(key, wasm.__wbindgen_malloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ptr1 = passArray8ToWasm0(nonce, wasm.__wbindgen_malloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ptr2 = passArray8ToWasm0(plaintext, wasm.__wbindgen_malloc);
+    const len2 = WASM_VECTOR_LEN;
+    const ret = wasm.encrypt_chacha20poly1305(ptr0, len0, ptr1, len1, ptr2, len2);
+    if (ret[3]) {
+        throw takeFromExternrefTable0(ret[2]);
+    }
+    var v4 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+    return v4;
 }
 
 async function __wbg_load(module, imports) {
@@ -98,7 +98,7 @@ class WasmGuardianLoader {
             }
         }
 
-        if (algorithm !== 'auto' && !this.supportedAlgorithms.includes(algorithm.toLowerCase())) {
+        if (algorithm !== 'auto' && !this.supportedAlgorithms.includes(this._normalizeAlgorithm(algorithm))) {
             throw new Error(`Unsupported algorithm: ${algorithm}. Supported: ${this.supportedAlgorithms.join(', ')}, auto`);
         }
     }
@@ -151,6 +151,27 @@ class WasmGuardianLoader {
         return new Uint8Array(arrayBuffer);
     }
 
+    /**
+     * Map common spellings ('AES-256-GCM', 'aes_gcm', 'ChaCha20-Poly1305', ...) to a supported name
+     * @private
+     */
+    _normalizeAlgorithm(algorithm) {
+        // Keep in sync with ALGORITHM_ALIASES in src/config.rs (checked by a Rust test)
+        const aliases = {
+            aesgcm: 'aes-gcm',
+            aes256gcm: 'aes-gcm',
+            aes256: 'aes-gcm',
+            aes128gcm: 'aes-gcm',
+            aes128: 'aes-gcm',
+            aes192gcm: 'aes-gcm',
+            aes192: 'aes-gcm',
+            chacha20poly1305: 'chacha20poly1305',
+            chacha20: 'chacha20poly1305',
+        };
+        const key = algorithm.toLowerCase().replace(/[-_\s]/g, '');
+        return aliases[key] || algorithm.toLowerCase();
+    }
+
     /**
      * Decrypt WASM data using the specified algorithm
      * @private
@@ -158,7 +179,7 @@ class WasmGuardianLoader {
     async _decryptWasm(encryptedData, keyHex, algorithm) {
         console.log('[WasmGuardianLoader] Decrypting WASM data...');
 
-        switch (algorithm.toLowerCase()) {
+        switch (this._normalizeAlgorithm(algorithm)) {
             case 'aes-gcm':
                 return await this._decryptAesGcm(encryptedData, keyHex);
             case 'chacha20poly1305':
@@ -169,12 +190,12 @@ class WasmGuardianLoader {
     }
 
     /**
-     * Decrypt using AES-GCM via SubtleCrypto API
+     * Decrypt using AES-GCM via SubtleCrypto API, falling back to the WASM helper
      * @private
      */
     async _decryptAesGcm(encryptedData, keyHex) {
         if (!window.crypto || !window.crypto.subtle) {
-            throw new Error('SubtleCrypto API not available in this environment');
+            return await this._decryptAesGcmWithHelper(encryptedData, keyHex);
         }
 
         try {
@@ -218,6 +239,45 @@ class WasmGuardianLoader {
         }
     }
 
+    /**
+     * Decrypt using AES-GCM via the WASM helper module, for environments
+     * without SubtleCrypto (some ServiceWorker or embedded JS engines)
+     * @private
+     */
+    async _decryptAesGcmWithHelper(encryptedData, keyHex) {
+        console.warn('[WasmGuardianLoader] SubtleCrypto API not available; using the WASM helper for AES-GCM, which is slower than native WebCrypto');
+
+        try {
+            // Load WASM decryption helper if not already loaded
+            if (!this.isHelperLoaded) {
+                await this._loadWasmDecryptorHelper();
+            }
+
+            const keyBytes = this._hexToUint8Array(keyHex);
+
+            // Extract IV and ciphertext (IV is first 12 bytes for AES-GCM)
+            const ivLength = 12;
+            if (encryptedData.length < ivLength) {
+                throw new Error('Encrypted data too short to contain IV');
+            }
+
+            const iv = encryptedData.slice(0, ivLength);
+            const ciphertext = encryptedData.slice(ivLength);
+
+            if (this.wasmDecryptorHelper.decryptAesGcm) {
+                // Using wrapper module
+                return await this.wasmDecryptorHelper.decryptAesGcm(keyBytes, iv, ciphertext);
+            } else if (this.wasmDecryptorHelper.decrypt_aes_gcm) {
+                // Using direct WASM module
+                return this.wasmDecryptorHelper.decrypt_aes_gcm(keyBytes, iv, ciphertext);
+            }
+            throw new Error('WASM helper does not provide AES-GCM decryption function');
+
+        } catch (error) {
+            throw new Error(`SubtleCrypto API not available and AES-GCM helper fallback failed: ${error.message}`);
+        }
+    }
+
     /**
      * Decrypt using ChaCha20-Poly1305 via WASM helper module
      * @private